@@ -2,8 +2,50 @@
 
 #[ink::contract]
 mod vesting_scheduler {
-    use ink::primitives::H160;
-    use ink::storage::Mapping;
+    use ink::env::hash::Keccak256;
+    use ink::prelude::vec::Vec;
+    use ink::primitives::{H160, U256};
+    use ink::storage::{Mapping, StorageVec};
+
+    /// Current storage schema version; bump it with every change to the storage layout
+    pub const CONTRACT_VERSION: u16 = 3;
+
+    /// Magic marker letting off-chain tooling recognise the storage layout: "VSC" followed
+    /// by the `CONTRACT_VERSION` digit, so the two can't drift apart
+    pub const STORAGE_SCHEMA: [u8; 4] = [b'V', b'S', b'C', b'0' + CONTRACT_VERSION as u8];
+
+    // The marker holds a single version digit
+    const _: () = assert!(CONTRACT_VERSION < 10);
+
+    /// Leading byte of `export_schedule` output, bumped whenever `VestingSchedule` changes
+    pub const EXPORT_FORMAT_VERSION: u8 = 2;
+
+    /// Maximum number of entries returned by a paged view
+    pub const MAX_PAGE_SIZE: u32 = 100;
+
+    /// Maximum number of addresses accepted by a batch view
+    pub const MAX_BATCH_SIZE: u32 = 200;
+
+    /// Milliseconds in a day
+    pub const MS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+    /// Basis-point denominator (10000 bps = 100%)
+    pub const MAX_BPS: u16 = 10_000;
+
+    /// Default number of claims kept per beneficiary in the on-chain history
+    pub const DEFAULT_MAX_CLAIM_HISTORY: u32 = 50;
+
+    /// Last millisecond the 4-digit year field can represent (9999-12-31 23:59:59.999 UTC)
+    pub const MAX_SUPPORTED_TIMESTAMP_MS: u64 = 253_402_300_799_999;
+
+    /// Token decimals assumed by `new()`
+    pub const DEFAULT_DECIMALS: u8 = 18;
+
+    /// Most decimals `format_amount` can render (u128 has at most 39 digits)
+    pub const MAX_DECIMALS: u8 = 38;
+
+    /// Fixed-point scale of `vesting_rate_per_ms` (18 fractional digits)
+    pub const RATE_SCALE: u128 = 1_000_000_000_000_000_000;
 
     // Defines a timestamp format
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
@@ -15,6 +57,103 @@ mod vesting_scheduler {
         pub hour: u8,
         pub minute: u8,
         pub second: u8,
+        pub millisecond: u16,
+    }
+
+    /// Shape of the vesting curve between start and end time
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum VestingKind {
+        /// Tokens vest evenly over time
+        #[default]
+        Linear,
+        /// Back-loaded: vested fraction is (elapsed/duration)^2
+        Quadratic,
+        /// Front-loaded: vested fraction is sqrt(elapsed/duration)
+        SqrtFront,
+        /// Tokens unlock in `intervals` equal tranches at evenly spaced boundaries
+        Stepped { intervals: u32 },
+        /// One period's worth unlocks at every `period_ms` boundary from start;
+        /// a final partial period unlocks at end time
+        Periodic { period_ms: u64 },
+        /// `1/months` unlocks on `day_of_month` (clamped to the month's last day)
+        /// of each of the `months` calendar months after start
+        Monthly { months: u32, day_of_month: u8 },
+    }
+
+    /// How fractional vested amounts are rounded to whole units
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum RoundingMode {
+        /// Round down (the last unit only vests at end_time)
+        #[default]
+        Floor,
+        /// Round up
+        Ceil,
+        /// Round half up
+        Nearest,
+    }
+
+    /// Which events the contract emits; each level includes the ones below it
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum EventVerbosity {
+        /// No events at all
+        None,
+        /// Core events: creations, claims, fees, revocations and ownership changes
+        #[default]
+        Standard,
+        /// Also the readable (formatted) events and `AdminAction` audit logs
+        Verbose,
+    }
+
+    /// Unit of the timestamps supplied by the chain and by callers
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum TimestampUnit {
+        /// Milliseconds since the Unix epoch
+        #[default]
+        Millis,
+        /// Seconds since the Unix epoch
+        Seconds,
+    }
+
+    /// Privileged operation reported by the `AdminAction` audit event
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[repr(u8)]
+    pub enum AdminActionKind {
+        CreateSchedule = 0,
+        ExtendSchedule = 1,
+        RevokeSchedule = 2,
+        MakeIrrevocable = 3,
+        SetAdmin = 4,
+        ProposeSchedule = 5,
+        ApproveSchedule = 6,
+        RenounceOwnership = 7,
+        UpdateConfig = 8,
+        WithdrawReclaimed = 9,
+        PruneSchedule = 10,
+        SweepUntracked = 11,
+        SuspendSchedule = 12,
+        ResumeSchedule = 13,
+        ReassignBeneficiary = 14,
+        UpdateAllowedRecipients = 15,
+        TopUpSchedule = 16,
+        CancelSchedule = 17,
+        ResyncCounters = 18,
+    }
+
+    /// Why a schedule's end time moved, as reported by `ScheduleTimeChanged`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[repr(u8)]
+    pub enum TimeChangeReason {
+        Extended = 0,
+        Resumed = 1,
+        Revoked = 2,
     }
 
     /// Defines a vesting schedule for a beneficiary
@@ -25,10 +164,83 @@ mod vesting_scheduler {
         pub total_amount: Balance,
         /// Amount claimed so far
         pub claimed_amount: Balance,
-        /// The starting time (should be in ms; we are using Unix epoch)
+        /// The starting time (always stored in ms; we are using Unix epoch)
         pub start_time: u64,
         /// The end time
         pub end_time: u64,
+        /// Curve used to release tokens between start and end
+        pub kind: VestingKind,
+        /// Human-readable label, zero-padded (e.g. "Q3-2024 advisor grant")
+        pub label: [u8; 32],
+        /// Amount released immediately at start_time (TGE unlock); the rest vests over time
+        pub initial_unlock: Balance,
+        /// Whether the owner may revoke the unvested part
+        pub revocable: bool,
+        /// Set once the unvested part has been revoked
+        pub revoked: bool,
+        /// When (ms) accrual was paused by `suspend_vesting`; `None` while vesting runs
+        pub suspended_at: Option<u64>,
+        /// Rounding for this grant; `None` follows the contract-wide mode
+        pub rounding: Option<RoundingMode>,
+        /// Guaranteed vesting per whole day since start; the curve is never allowed to
+        /// lag `min_vest_per_day * elapsed_days` (0 = no floor)
+        pub min_vest_per_day: Balance,
+    }
+
+    /// Optional settings for `create_vesting_schedule`; the default is an unlabelled, revocable
+    /// linear grant without initial unlock or floor that follows the contract-wide rounding
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct ScheduleParams {
+        /// Human-readable label, zero-padded to 32 bytes
+        pub label: [u8; 32],
+        /// Part of `total_amount` released at `start_time` (0 for none)
+        pub initial_unlock: Balance,
+        /// Curve used to release tokens between start and end
+        pub kind: VestingKind,
+        /// Rounding fixed for this grant; `None` follows the contract-wide mode
+        pub rounding: Option<RoundingMode>,
+        /// Guaranteed vesting per whole day since start, capped at the total (0 = no floor)
+        pub min_vest_per_day: Balance,
+        /// Whether the owner may revoke the unvested part; `false` is fixed for the grant's life
+        pub revocable: bool,
+    }
+
+    // Grants are revocable unless created otherwise
+    impl Default for ScheduleParams {
+        fn default() -> Self {
+            Self {
+                label: [0u8; 32],
+                initial_unlock: 0,
+                kind: VestingKind::default(),
+                rounding: None,
+                min_vest_per_day: 0,
+                revocable: true,
+            }
+        }
+    }
+
+    /// A schedule with its start and end dates as "YYYY-MM-DD HH:MM:SS"
+    pub type ReadableSchedule = (VestingSchedule, [u8; 19], [u8; 19]);
+
+    // Schedules are revocable unless made irrevocable
+    impl Default for VestingSchedule {
+        fn default() -> Self {
+            Self {
+                total_amount: 0,
+                claimed_amount: 0,
+                start_time: 0,
+                end_time: 0,
+                kind: VestingKind::default(),
+                label: [0u8; 32],
+                initial_unlock: 0,
+                revocable: true,
+                revoked: false,
+                suspended_at: None,
+                rounding: None,
+                min_vest_per_day: 0,
+            }
+        }
     }
 
     #[ink(storage)]
@@ -37,6 +249,85 @@ mod vesting_scheduler {
         schedules: Mapping<H160, VestingSchedule>,
         /// Owner of the contract
         owner: H160,
+        /// How far (in ms) a new schedule's start may lie before the current block; 0 disables the check
+        max_backdate_ms: u64,
+        /// Set while a claim is in progress to block re-entrant calls
+        locked: bool,
+        /// Rounding applied to vested amounts
+        rounding_mode: RoundingMode,
+        /// Most recent claims per beneficiary as (timestamp, amount), oldest first
+        claim_history: Mapping<H160, Vec<(u64, Balance)>>,
+        /// Number of claims kept per beneficiary; 0 disables the history
+        max_claim_history: u32,
+        /// Maps a beneficiary to the operator allowed to claim on their behalf
+        operators: Mapping<H160, H160>,
+        /// Unit of block timestamps and caller-supplied times; stored times are ms
+        timestamp_unit: TimestampUnit,
+        /// Fee skimmed from each claim, in basis points
+        claim_fee_bps: u16,
+        /// Account credited with claim fees
+        fee_recipient: H160,
+        /// Storage schema version the contract was deployed with
+        version: u16,
+        /// Every address holding a schedule, in creation order; kept out of the root
+        /// storage cell so claims don't load the whole list
+        beneficiaries: StorageVec<H160>,
+        /// Time (ms) of each beneficiary's last successful claim
+        last_claim_time: Mapping<H160, u64>,
+        /// Minimum time (ms) between two claims of the same beneficiary; 0 disables the cooldown
+        min_claim_interval_ms: u64,
+        /// Next expected nonce for each beneficiary's signed (meta) claims
+        nonces: Mapping<H160, u64>,
+        /// Longest allowed schedule (ms); 0 means unlimited
+        max_duration_ms: u64,
+        /// Shortest allowed schedule (ms); 0 disables the check
+        min_duration_ms: u64,
+        /// Accounts allowed to propose and approve schedules
+        admins: Mapping<H160, ()>,
+        /// Pending proposals as (schedule, beneficiary, first approval)
+        proposals: Mapping<u32, (VestingSchedule, H160, Option<H160>)>,
+        /// Id handed to the next proposal
+        next_proposal_id: u32,
+        /// Running sum of granted tokens, net of revoked and cancelled amounts
+        total_vested_allocated: Balance,
+        /// Running sum of tokens claimed (including fees)
+        total_claimed: Balance,
+        /// Number of successful claims per beneficiary
+        claim_count: Mapping<H160, u32>,
+        /// Tokens (including fees) each beneficiary has claimed over all their grants
+        lifetime_claimed: Mapping<H160, Balance>,
+        /// Decimal precision of the vested token
+        decimals: u8,
+        /// Which events are emitted
+        event_verbosity: EventVerbosity,
+        /// Tokens released by revocations and cancellations, not yet withdrawn by the owner
+        reclaimable: Balance,
+        /// Cumulative value received through `fund()`
+        total_funded: Balance,
+        /// Whether per-beneficiary views are limited to the owner and the beneficiary
+        private_schedules: bool,
+        /// Smallest fee charged on any claim (capped at the claimed amount); 0 disables it
+        min_fee: Balance,
+        /// Caller-supplied creation ids already used, mapped to the beneficiary they created
+        used_schedule_ids: Mapping<u32, H160>,
+        /// How long (ms) after its end a schedule must be left before it can be pruned
+        prune_grace_ms: u64,
+        /// Whitelisted claim recipients per (beneficiary, recipient)
+        allowed_recipients: Mapping<(H160, H160), bool>,
+        /// Beneficiaries whose redirected claims are restricted to the whitelist
+        restricted_recipients: Mapping<H160, ()>,
+        /// Whether schedules that have already ended may be created (backfills)
+        allow_past_end: bool,
+        /// Most addresses that may hold a schedule at once; 0 means unlimited
+        max_beneficiaries: u32,
+        /// Claimed tokens (net of fees) still held by the contract for their claimants
+        claimed_unpaid: Balance,
+        /// Cumulative value paid out by `withdraw_reclaimed` and `withdraw_fees`
+        total_withdrawn: Balance,
+        /// Claim fees credited to each fee recipient and not yet withdrawn
+        fee_balances: Mapping<H160, Balance>,
+        /// Sum of `fee_balances`
+        unpaid_fees: Balance,
     }
 
     #[ink(event)]
@@ -48,13 +339,44 @@ mod vesting_scheduler {
         end_time: u64,
     }
 
+    // Mirrors VestingCreated with readable dates so indexers don't have to convert
+    #[ink(event)]
+    pub struct VestingCreatedReadable {
+        #[ink(topic)]
+        beneficiary: H160,
+        total_amount: Balance,
+        /// Readable format: [Y,Y,Y,Y,-,M,M,-,D,D, ,H,H,:,M,M,:,S,S]
+        start_readable: [u8; 19],
+        /// Readable format: [Y,Y,Y,Y,-,M,M,-,D,D, ,H,H,:,M,M,:,S,S]
+        end_readable: [u8; 19],
+    }
+
     #[ink(event)]
     pub struct TokensClaimed {
         #[ink(topic)]
         beneficiary: H160,
+        /// Account the tokens were claimed to; the beneficiary unless redirected
+        #[ink(topic)]
+        recipient: H160,
         amount: Balance,
+        /// Order of magnitude of `amount` in steps of 1000 (0: <1k, 1: <1M, 2: <1B, ...)
+        /// so indexers can filter large claims by topic
+        #[ink(topic)]
+        amount_bucket: u8,
         claimed_at: u64,
     }
+    /// Follows `TokensClaimed` for claims tagged with an accounting reference
+    #[ink(event)]
+    pub struct TokensClaimedWithMemo {
+        #[ink(topic)]
+        beneficiary: H160,
+        /// Caller-chosen reference (e.g. a payroll id); only ever logged, never stored
+        #[ink(topic)]
+        memo: [u8; 32],
+        /// Amount credited, net of the claim fee
+        amount: Balance,
+    }
+
     // This event has readable timestamp for demo
     #[ink(event)]
     pub struct TokensClaimedReadable {
@@ -62,8 +384,156 @@ mod vesting_scheduler {
         beneficiary: H160,
         amount: Balance,
         claimed_at: u64,
-        /// Readable format: [Y,Y,Y,Y,-,M,M,-,D,D, ,H,H,:,M,M,:,S,S]
-        claimed_at_readable: [u8; 19],
+        /// Readable format with milliseconds, so claims within one second stay distinct:
+        /// [Y,Y,Y,Y,-,M,M,-,D,D, ,H,H,:,M,M,:,S,S,.,m,m,m]
+        claimed_at_readable: [u8; 23],
+        /// `amount` with the token's decimal point, zero-padded (see `format_amount`)
+        amount_readable: [u8; 40],
+    }
+
+    /// The fee share of a claim, credited to the fee recipient's `withdraw_fees` balance
+    #[ink(event)]
+    pub struct FeeCollected {
+        #[ink(topic)]
+        beneficiary: H160,
+        #[ink(topic)]
+        recipient: H160,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ScheduleTransferred {
+        #[ink(topic)]
+        from: H160,
+        #[ink(topic)]
+        to: H160,
+    }
+
+    #[ink(event)]
+    pub struct BeneficiaryReassigned {
+        #[ink(topic)]
+        from: H160,
+        #[ink(topic)]
+        to: H160,
+    }
+
+    #[ink(event)]
+    pub struct VestingToppedUp {
+        #[ink(topic)]
+        beneficiary: H160,
+        additional: Balance,
+        new_total: Balance,
+    }
+
+    #[ink(event)]
+    pub struct VestingCancelled {
+        #[ink(topic)]
+        beneficiary: H160,
+        /// Allocation released by the cancellation
+        total_amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct FeeConfigUpdated {
+        #[ink(topic)]
+        recipient: H160,
+        /// New claim fee in basis points
+        bps: u16,
+    }
+
+    #[ink(event)]
+    pub struct ScheduleTimeChanged {
+        #[ink(topic)]
+        beneficiary: H160,
+        old_end_time: u64,
+        new_end_time: u64,
+        /// `TimeChangeReason` code
+        reason: u8,
+    }
+
+    /// Allocation counters recomputed from the stored schedules
+    #[ink(event)]
+    pub struct CountersResynced {
+        old_total_allocated: Balance,
+        new_total_allocated: Balance,
+        old_total_claimed: Balance,
+        new_total_claimed: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SchedulePruned {
+        #[ink(topic)]
+        beneficiary: H160,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        previous_owner: H160,
+        #[ink(topic)]
+        new_owner: H160,
+    }
+
+    #[ink(event)]
+    pub struct VestingRevoked {
+        #[ink(topic)]
+        beneficiary: H160,
+        /// Amount that stays with the beneficiary
+        vested_amount: Balance,
+        /// Amount cancelled by the revocation
+        unvested_amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct VestingSuspended {
+        #[ink(topic)]
+        beneficiary: H160,
+        suspended_at: u64,
+    }
+
+    #[ink(event)]
+    pub struct VestingResumed {
+        #[ink(topic)]
+        beneficiary: H160,
+        /// How long (ms) accrual was paused; the schedule was shifted by this much
+        paused_ms: u64,
+        /// The schedule's end time after the shift
+        end_time: u64,
+    }
+
+    #[ink(event)]
+    pub struct ReclaimedWithdrawn {
+        #[ink(topic)]
+        recipient: H160,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct FeesWithdrawn {
+        #[ink(topic)]
+        recipient: H160,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct FundsReceived {
+        #[ink(topic)]
+        from: H160,
+        amount: Balance,
+        /// Cumulative funding after this deposit
+        total_funded: Balance,
+    }
+
+    /// Audit trail entry emitted by every owner/admin state change
+    #[ink(event)]
+    pub struct AdminAction {
+        /// `AdminActionKind` code
+        action_kind: u8,
+        #[ink(topic)]
+        caller: H160,
+        /// Address affected by the action; zero for contract-wide settings
+        #[ink(topic)]
+        target: H160,
     }
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[derive(Debug, PartialEq, Eq)]
@@ -78,24 +548,302 @@ mod vesting_scheduler {
         VestingNotStarted,
         /// No tokens available to claim
         NoTokensAvailable,
+        /// Start time lies further in the past than the configured backdate window
+        StartTimeTooFarInPast,
+        /// A claim is already in progress
+        Reentrancy,
+        /// Target address already has a vesting schedule
+        ScheduleAlreadyExists,
+        /// Caller is neither the beneficiary nor their approved operator
+        UnauthorizedOperator,
+        /// Initial unlock exceeds the total amount
+        InvalidInitialUnlock,
+        /// Fee above 10000 basis points
+        InvalidFee,
+        /// Arithmetic overflow in the vesting calculation
+        Overflow,
+        /// Minimum interval since the last claim has not elapsed
+        ClaimTooSoon,
+        /// Signature doesn't recover to the beneficiary
+        InvalidSignature,
+        /// Nonce doesn't match the beneficiary's next expected nonce
+        BadNonce,
+        /// Stepped, periodic or monthly schedule without any interval
+        InvalidIntervals,
+        /// Schedule is longer than the configured maximum duration
+        DurationTooLong,
+        /// Schedule is shorter than the configured minimum duration
+        DurationTooShort,
+        /// No pending proposal with this id
+        ProposalNotFound,
+        /// The caller already approved this proposal
+        AlreadyApproved,
+        /// The schedule is irrevocable
+        NotRevocable,
+        /// The schedule has already been revoked
+        AlreadyRevoked,
+        /// The schedule still has unclaimed tokens
+        NotFullyClaimed,
+        /// Transferring funds out of the contract failed
+        TransferFailed,
+        /// Schedules can't be created for the zero address
+        InvalidBeneficiary,
+        /// Schedules must vest a non-zero amount
+        InvalidAmount,
+        /// The claimable amount differs from what the caller expected
+        SlippageExceeded,
+        /// The schedule is suspended
+        ScheduleSuspended,
+        /// The schedule is not suspended
+        NotSuspended,
+        /// The creation id has already been used
+        IdAlreadyExists,
+        /// The block timestamp is 0 (genesis or an unset test clock)
+        TimeNotAvailable,
+        /// The schedule ended too recently to be pruned
+        PruneTooSoon,
+        /// The recipient is not whitelisted for the beneficiary's claims
+        RecipientNotAllowed,
+        /// The schedule has already started
+        VestingAlreadyStarted,
+        /// The schedule would end at or before the current block
+        EndTimeInPast,
+        /// A date has an out-of-range field, or a year outside 1970-9999
+        InvalidDateTime,
+        /// Creating the schedule would exceed the beneficiary cap
+        TooManyBeneficiaries,
+        /// The operation isn't supported for the schedule's vesting curve
+        UnsupportedKind,
     }
 
     pub type Result<T> = core::result::Result<T, Error>;
 
+    impl Error {
+        /// Every variant, in code order
+        pub const ALL: [Error; 38] = [
+            Error::Unauthorized,
+            Error::InvalidTimeRange,
+            Error::NoVestingSchedule,
+            Error::VestingNotStarted,
+            Error::NoTokensAvailable,
+            Error::StartTimeTooFarInPast,
+            Error::Reentrancy,
+            Error::ScheduleAlreadyExists,
+            Error::UnauthorizedOperator,
+            Error::InvalidInitialUnlock,
+            Error::InvalidFee,
+            Error::Overflow,
+            Error::ClaimTooSoon,
+            Error::InvalidSignature,
+            Error::BadNonce,
+            Error::InvalidIntervals,
+            Error::DurationTooLong,
+            Error::DurationTooShort,
+            Error::ProposalNotFound,
+            Error::AlreadyApproved,
+            Error::NotRevocable,
+            Error::AlreadyRevoked,
+            Error::NotFullyClaimed,
+            Error::TransferFailed,
+            Error::InvalidBeneficiary,
+            Error::InvalidAmount,
+            Error::SlippageExceeded,
+            Error::ScheduleSuspended,
+            Error::NotSuspended,
+            Error::IdAlreadyExists,
+            Error::TimeNotAvailable,
+            Error::PruneTooSoon,
+            Error::RecipientNotAllowed,
+            Error::VestingAlreadyStarted,
+            Error::EndTimeInPast,
+            Error::InvalidDateTime,
+            Error::TooManyBeneficiaries,
+            Error::UnsupportedKind,
+        ];
+
+        /// Stable numeric code for off-chain consumers (0 is never used)
+        /// Codes are append-only: existing variants keep theirs, new variants take the next one
+        pub fn code(&self) -> u16 {
+            match self {
+                Error::Unauthorized => 1,
+                Error::InvalidTimeRange => 2,
+                Error::NoVestingSchedule => 3,
+                Error::VestingNotStarted => 4,
+                Error::NoTokensAvailable => 5,
+                Error::StartTimeTooFarInPast => 6,
+                Error::Reentrancy => 7,
+                Error::ScheduleAlreadyExists => 8,
+                Error::UnauthorizedOperator => 9,
+                Error::InvalidInitialUnlock => 10,
+                Error::InvalidFee => 11,
+                Error::Overflow => 12,
+                Error::ClaimTooSoon => 13,
+                Error::InvalidSignature => 14,
+                Error::BadNonce => 15,
+                Error::InvalidIntervals => 16,
+                Error::DurationTooLong => 17,
+                Error::DurationTooShort => 18,
+                Error::ProposalNotFound => 19,
+                Error::AlreadyApproved => 20,
+                Error::NotRevocable => 21,
+                Error::AlreadyRevoked => 22,
+                Error::NotFullyClaimed => 23,
+                Error::TransferFailed => 24,
+                Error::InvalidBeneficiary => 25,
+                Error::InvalidAmount => 26,
+                Error::SlippageExceeded => 27,
+                Error::ScheduleSuspended => 28,
+                Error::NotSuspended => 29,
+                Error::IdAlreadyExists => 30,
+                Error::TimeNotAvailable => 31,
+                Error::PruneTooSoon => 32,
+                Error::RecipientNotAllowed => 33,
+                Error::VestingAlreadyStarted => 34,
+                Error::EndTimeInPast => 35,
+                Error::InvalidDateTime => 36,
+                Error::TooManyBeneficiaries => 37,
+                Error::UnsupportedKind => 38,
+            }
+        }
+
+        /// Variant name, as listed by `error_code_table`
+        pub fn name(&self) -> &'static str {
+            match self {
+                Error::Unauthorized => "Unauthorized",
+                Error::InvalidTimeRange => "InvalidTimeRange",
+                Error::NoVestingSchedule => "NoVestingSchedule",
+                Error::VestingNotStarted => "VestingNotStarted",
+                Error::NoTokensAvailable => "NoTokensAvailable",
+                Error::StartTimeTooFarInPast => "StartTimeTooFarInPast",
+                Error::Reentrancy => "Reentrancy",
+                Error::ScheduleAlreadyExists => "ScheduleAlreadyExists",
+                Error::UnauthorizedOperator => "UnauthorizedOperator",
+                Error::InvalidInitialUnlock => "InvalidInitialUnlock",
+                Error::InvalidFee => "InvalidFee",
+                Error::Overflow => "Overflow",
+                Error::ClaimTooSoon => "ClaimTooSoon",
+                Error::InvalidSignature => "InvalidSignature",
+                Error::BadNonce => "BadNonce",
+                Error::InvalidIntervals => "InvalidIntervals",
+                Error::DurationTooLong => "DurationTooLong",
+                Error::DurationTooShort => "DurationTooShort",
+                Error::ProposalNotFound => "ProposalNotFound",
+                Error::AlreadyApproved => "AlreadyApproved",
+                Error::NotRevocable => "NotRevocable",
+                Error::AlreadyRevoked => "AlreadyRevoked",
+                Error::NotFullyClaimed => "NotFullyClaimed",
+                Error::TransferFailed => "TransferFailed",
+                Error::InvalidBeneficiary => "InvalidBeneficiary",
+                Error::InvalidAmount => "InvalidAmount",
+                Error::SlippageExceeded => "SlippageExceeded",
+                Error::ScheduleSuspended => "ScheduleSuspended",
+                Error::NotSuspended => "NotSuspended",
+                Error::IdAlreadyExists => "IdAlreadyExists",
+                Error::TimeNotAvailable => "TimeNotAvailable",
+                Error::PruneTooSoon => "PruneTooSoon",
+                Error::RecipientNotAllowed => "RecipientNotAllowed",
+                Error::VestingAlreadyStarted => "VestingAlreadyStarted",
+                Error::EndTimeInPast => "EndTimeInPast",
+                Error::InvalidDateTime => "InvalidDateTime",
+                Error::TooManyBeneficiaries => "TooManyBeneficiaries",
+                Error::UnsupportedKind => "UnsupportedKind",
+            }
+        }
+    }
+
     impl VestingScheduler {
         #[ink(constructor)]
         pub fn new() -> Self {
+            Self::new_with_config(TimestampUnit::Millis, DEFAULT_DECIMALS, false)
+        }
+
+        /// Creates the contract for a chain whose block timestamps use `timestamp_unit`
+        /// Times passed to messages use the same unit; they are stored in ms
+        /// `decimals` - Decimal precision of the vested token, for display only
+        /// `private_schedules` - Restrict schedule views to the owner and the beneficiary
+        #[ink(constructor)]
+        pub fn new_with_config(
+            timestamp_unit: TimestampUnit,
+            decimals: u8,
+            private_schedules: bool,
+        ) -> Self {
             Self {
                 schedules: Mapping::default(),
                 owner: Self::env().caller(),
+                fee_recipient: Self::env().caller(),
+                max_backdate_ms: 0,
+                locked: false,
+                rounding_mode: RoundingMode::Floor,
+                claim_history: Mapping::default(),
+                max_claim_history: DEFAULT_MAX_CLAIM_HISTORY,
+                operators: Mapping::default(),
+                timestamp_unit,
+                claim_fee_bps: 0,
+                version: CONTRACT_VERSION,
+                beneficiaries: StorageVec::new(),
+                last_claim_time: Mapping::default(),
+                min_claim_interval_ms: 0,
+                nonces: Mapping::default(),
+                max_duration_ms: 0,
+                min_duration_ms: 0,
+                admins: Mapping::default(),
+                proposals: Mapping::default(),
+                next_proposal_id: 0,
+                total_vested_allocated: 0,
+                total_claimed: 0,
+                claim_count: Mapping::default(),
+                lifetime_claimed: Mapping::default(),
+                decimals,
+                event_verbosity: EventVerbosity::Standard,
+                reclaimable: 0,
+                total_funded: 0,
+                private_schedules,
+                min_fee: 0,
+                used_schedule_ids: Mapping::default(),
+                prune_grace_ms: 0,
+                allowed_recipients: Mapping::default(),
+                restricted_recipients: Mapping::default(),
+                allow_past_end: false,
+                max_beneficiaries: 0,
+                claimed_unpaid: 0,
+                total_withdrawn: 0,
+                fee_balances: Mapping::default(),
+                unpaid_fees: 0,
+            }
+        }
+
+        /// Creates the contract (ms timestamps) and its first linear grant in one step
+        /// Constructors can't return an error, so an invalid schedule (e.g. a bad time
+        /// range or zero amount) traps and the deployment is reverted
+        /// `start_time` - Unix timestamp (ms) when vesting starts; 0 means now
+        #[ink(constructor)]
+        pub fn new_with_schedule(
+            beneficiary: H160,
+            total_amount: Balance,
+            start_time: u64,
+            end_time: u64,
+        ) -> Self {
+            let mut contract = Self::new();
+            let schedule = VestingSchedule {
+                total_amount,
+                start_time: contract.resolve_start_time(start_time),
+                end_time,
+                ..Default::default()
+            };
+            if let Err(error) = contract.add_schedule(beneficiary, schedule) {
+                panic!("invalid initial schedule: {error:?}");
             }
+            contract
         }
 
         /// Creates a vesting schedule for a beneficiary
         /// `beneficiary` - Account that will receive vested tokens
         /// `total_amount` - Total tokens to vest
-        /// `start_time` - Unix timestamp in milliseconds when vesting starts
-        /// `end_time` - Unix timestamp in milliseconds when vesting ends
+        /// `start_time` - Unix timestamp (configured unit) when vesting starts; 0 means now
+        /// `end_time` - Unix timestamp (configured unit) when vesting ends
+        /// `params` - Label, initial unlock, curve, rounding, floor and revocability;
+        /// see `ScheduleParams`
+        /// Fails with `ScheduleAlreadyExists` if the beneficiary already holds a schedule
         #[ink(message)]
         pub fn create_vesting_schedule(
             &mut self,
@@ -103,102 +851,1752 @@ mod vesting_scheduler {
             total_amount: Balance,
             start_time: u64,
             end_time: u64,
+            params: ScheduleParams,
         ) -> Result<()> {
-            if self.env().caller() != self.owner {
-                return Err(Error::Unauthorized);
-            }
-            if start_time >= end_time {
-                return Err(Error::InvalidTimeRange);
-            }
+            self.ensure_owner()?;
             let schedule = VestingSchedule {
                 total_amount,
-                claimed_amount: 0,
-                start_time,
-                end_time,
+                start_time: self.resolve_start_time(start_time),
+                end_time: self.to_millis(end_time),
+                kind: params.kind,
+                label: params.label,
+                initial_unlock: params.initial_unlock,
+                rounding: params.rounding,
+                min_vest_per_day: params.min_vest_per_day,
+                revocable: params.revocable,
+                ..Default::default()
             };
-            self.schedules.insert(beneficiary, &schedule);
-            self.env().emit_event(VestingCreated {
-                beneficiary,
-                total_amount,
-                start_time,
-                end_time,
-            });
-            Ok(())
+            self.add_schedule(beneficiary, schedule)
         }
 
+        /// Creates an unlabelled linear schedule under a caller-chosen id (owner only)
+        /// Each id can be used once, so a retried transaction can't create the grant twice
+        /// `id` - Deterministic id from the off-chain orchestrator
         #[ink(message)]
-        pub fn claim_vested(&mut self) -> Result<Balance> {
-            let caller = self.env().caller();
-            let current_time = self.env().block_timestamp();
-
-            // Retrieve the vesting schedule
-            let mut schedule = self.schedules.get(caller).ok_or(Error::NoVestingSchedule)?;
-
-            // Confirm that vesting has started
-            if current_time < schedule.start_time {
-                return Err(Error::VestingNotStarted);
-            }
-
-            // Calculate vested amount
-            let vested_amount = self.calculate_vested_amount(&schedule, current_time);
-            let claimable = vested_amount.saturating_sub(schedule.claimed_amount);
-
-            if claimable == 0 {
-                return Err(Error::NoTokensAvailable);
+        pub fn create_vesting_schedule_with_id(
+            &mut self,
+            id: u32,
+            beneficiary: H160,
+            total_amount: Balance,
+            start_time: u64,
+            end_time: u64,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            if self.used_schedule_ids.contains(id) {
+                return Err(Error::IdAlreadyExists);
             }
-
-            // Update claimed amount
-            schedule.claimed_amount = schedule.claimed_amount.saturating_add(claimable);
-            self.schedules.insert(caller, &schedule);
-
-            // Emit event(standard event)
-            self.env().emit_event(TokensClaimed {
-                beneficiary: caller,
-                amount: claimable,
-                claimed_at: current_time,
-            });
-            // Emit event with readable timestamp (demonstrates on-chain conversion)
-            let dt = self.timestamp_to_datetime(current_time);
-            self.env().emit_event(TokensClaimedReadable {
-                beneficiary: caller,
-                amount: claimable,
-                claimed_at: current_time,
-                claimed_at_readable: self.format_datetime(dt),
-            });
-
-            Ok(claimable)
+            let schedule = VestingSchedule {
+                total_amount,
+                start_time: self.resolve_start_time(start_time),
+                end_time: self.to_millis(end_time),
+                ..Default::default()
+            };
+            self.add_schedule(beneficiary, schedule)?;
+            self.used_schedule_ids.insert(id, &beneficiary);
+            Ok(())
         }
 
-        /// View function to get vesting schedule with readable dates
+        /// Dry run of `create_vesting_schedule` for an unlabelled linear grant without unlock
+        /// Runs every creation-time check (beneficiary, amount, time range, duration bounds,
+        /// backdating) without changing state, so front-ends can show errors inline
         #[ink(message)]
-        pub fn get_vesting_schedule_readable(
+        pub fn validate_vesting_params(
             &self,
             beneficiary: H160,
-        ) -> Option<(VestingSchedule, [u8; 19], [u8; 19])> {
-            let schedule = self.schedules.get(beneficiary)?;
-
-            let start_dt = self.timestamp_to_datetime(schedule.start_time);
-            let end_dt = self.timestamp_to_datetime(schedule.end_time);
+            total_amount: Balance,
+            start_time: u64,
+            end_time: u64,
+        ) -> Result<()> {
+            let schedule = VestingSchedule {
+                total_amount,
+                start_time: self.resolve_start_time(start_time),
+                end_time: self.to_millis(end_time),
+                ..Default::default()
+            };
+            self.validate_schedule(beneficiary, &schedule)
+        }
 
-            Some((
-                schedule,
-                self.format_datetime(start_dt),
-                self.format_datetime(end_dt),
-            ))
+        /// Get the beneficiary created under a caller-chosen id, if the id was used
+        #[ink(message)]
+        pub fn get_schedule_id(&self, id: u32) -> Option<H160> {
+            self.used_schedule_ids.get(id)
         }
 
-        /// Get vesting schedule (raw timestamps only)
+        /// Creates an unlabelled vesting schedule that releases tokens along the given curve
+        /// A `start_time` of 0 starts vesting at the current block
+        /// `kind` - Shape of the vesting curve (linear, back-loaded or front-loaded)
         #[ink(message)]
-        pub fn get_vesting_schedule(&self, beneficiary: H160) -> Option<VestingSchedule> {
-            self.schedules.get(beneficiary)
+        pub fn create_vesting_schedule_with_kind(
+            &mut self,
+            beneficiary: H160,
+            total_amount: Balance,
+            start_time: u64,
+            end_time: u64,
+            kind: VestingKind,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            let schedule = VestingSchedule {
+                total_amount,
+                start_time: self.resolve_start_time(start_time),
+                end_time: self.to_millis(end_time),
+                kind,
+                ..Default::default()
+            };
+            self.add_schedule(beneficiary, schedule)
         }
 
-        // Timestamp Conversion Functions (no_std compatible)
-        /// Convert Unix timestamp (milliseconds) to DateTime
-        /// This demonstrates on-chain conversion but is typically done off-chain
-        fn timestamp_to_datetime(&self, timestamp_ms: u64) -> DateTime {
-            // Convert milliseconds to seconds
-            let timestamp = timestamp_ms / 1000;
+        /// Grants or revokes the admin role used for two-signer proposals (owner only)
+        #[ink(message)]
+        pub fn set_admin(&mut self, account: H160, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            if enabled {
+                self.admins.insert(account, &());
+            } else {
+                self.admins.remove(account);
+            }
+            self.record_admin_action(AdminActionKind::SetAdmin, account);
+            Ok(())
+        }
+
+        /// Check whether an account holds the admin role
+        #[ink(message)]
+        pub fn is_admin(&self, account: H160) -> bool {
+            self.admins.contains(account)
+        }
+
+        /// Proposes a schedule that is only created once two distinct admins approve it
+        /// Times are in the configured unit, a start time of 0 meaning now; returns the proposal id
+        #[ink(message)]
+        pub fn propose_vesting(
+            &mut self,
+            beneficiary: H160,
+            total_amount: Balance,
+            start_time: u64,
+            end_time: u64,
+        ) -> Result<u32> {
+            self.ensure_admin()?;
+            let schedule = VestingSchedule {
+                total_amount,
+                start_time: self.resolve_start_time(start_time),
+                end_time: self.to_millis(end_time),
+                ..Default::default()
+            };
+            let id = self.next_proposal_id;
+            self.next_proposal_id = id.checked_add(1).ok_or(Error::Overflow)?;
+            self.proposals
+                .insert(id, &(schedule, beneficiary, None::<H160>));
+            self.record_admin_action(AdminActionKind::ProposeSchedule, beneficiary);
+            Ok(id)
+        }
+
+        /// Approves a proposal; the second distinct admin's approval creates the schedule
+        #[ink(message)]
+        pub fn approve_vesting(&mut self, id: u32) -> Result<()> {
+            self.ensure_admin()?;
+            let caller = self.env().caller();
+            let (schedule, beneficiary, approval) =
+                self.proposals.get(id).ok_or(Error::ProposalNotFound)?;
+            if approval == Some(caller) {
+                return Err(Error::AlreadyApproved);
+            }
+            self.record_admin_action(AdminActionKind::ApproveSchedule, beneficiary);
+
+            match approval {
+                None => {
+                    self.proposals
+                        .insert(id, &(schedule, beneficiary, Some(caller)));
+                    Ok(())
+                }
+                Some(_) => {
+                    self.add_schedule(beneficiary, schedule)?;
+                    self.proposals.remove(id);
+                    Ok(())
+                }
+            }
+        }
+
+        /// Get a pending proposal as (schedule, beneficiary, first approval)
+        #[ink(message)]
+        pub fn get_proposal(&self, id: u32) -> Option<(VestingSchedule, H160, Option<H160>)> {
+            self.proposals.get(id)
+        }
+
+        /// Creates an unlabelled schedule unlocking one period's worth at each period boundary
+        /// `period_ms` - Period length (configured unit, e.g. a month); any final partial
+        /// period unlocks at `end_time`, so the total always fully vests
+        #[ink(message)]
+        pub fn create_periodic_vesting(
+            &mut self,
+            beneficiary: H160,
+            total_amount: Balance,
+            start_time: u64,
+            end_time: u64,
+            period_ms: u64,
+        ) -> Result<()> {
+            self.create_vesting_schedule_with_kind(
+                beneficiary,
+                total_amount,
+                start_time,
+                end_time,
+                VestingKind::Periodic {
+                    period_ms: self.to_millis(period_ms),
+                },
+            )
+        }
+
+        /// Creates a stepped schedule from a rate, e.g. 500 tokens a month for 24 months
+        /// The total is `amount_per_period * periods`, each unlocking at a period boundary
+        /// `start_time` / `period_ms` - Configured unit; a start time of 0 means now
+        #[ink(message)]
+        pub fn create_vesting_from_rate(
+            &mut self,
+            beneficiary: H160,
+            amount_per_period: Balance,
+            periods: u32,
+            start_time: u64,
+            period_ms: u64,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            if periods == 0 {
+                return Err(Error::InvalidIntervals);
+            }
+            let total_amount = amount_per_period
+                .checked_mul(Balance::from(periods))
+                .ok_or(Error::Overflow)?;
+            let start_time = self.resolve_start_time(start_time);
+            let end_time = self
+                .to_millis(period_ms)
+                .checked_mul(u64::from(periods))
+                .and_then(|duration| start_time.checked_add(duration))
+                .ok_or(Error::Overflow)?;
+            let schedule = VestingSchedule {
+                total_amount,
+                start_time,
+                end_time,
+                kind: VestingKind::Stepped { intervals: periods },
+                ..Default::default()
+            };
+            self.add_schedule(beneficiary, schedule)
+        }
+
+        /// Creates a salary-style schedule unlocking `total_amount / months` on `day_of_month`
+        /// of each following calendar month; short months unlock on their last day
+        /// `start_time` - Unix timestamp (configured unit); unlocks keep its time of day
+        #[ink(message)]
+        pub fn create_monthly_vesting(
+            &mut self,
+            beneficiary: H160,
+            total_amount: Balance,
+            start_time: u64,
+            months: u32,
+            day_of_month: u8,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            if months == 0 {
+                return Err(Error::InvalidIntervals);
+            }
+            if day_of_month == 0 || day_of_month > 31 {
+                return Err(Error::InvalidTimeRange);
+            }
+            let start_time = self.resolve_start_time(start_time);
+            let schedule = VestingSchedule {
+                total_amount,
+                start_time,
+                // The last unlock completes the schedule
+                end_time: self.month_boundary(start_time, months, day_of_month),
+                kind: VestingKind::Monthly {
+                    months,
+                    day_of_month,
+                },
+                ..Default::default()
+            };
+            self.add_schedule(beneficiary, schedule)
+        }
+
+        /// Creates an unlabelled linear schedule from calendar dates (UTC)
+        #[ink(message)]
+        pub fn create_vesting_schedule_from_dates(
+            &mut self,
+            beneficiary: H160,
+            total_amount: Balance,
+            start: DateTime,
+            end: DateTime,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            let schedule = VestingSchedule {
+                total_amount,
+                start_time: self.datetime_to_timestamp(start)?,
+                end_time: self.datetime_to_timestamp(end)?,
+                ..Default::default()
+            };
+            self.add_schedule(beneficiary, schedule)
+        }
+
+        /// Moves a schedule's end time later, stretching the unvested remainder (owner only)
+        /// Tokens vested so far stay vested: if vesting is underway the schedule is re-anchored
+        /// at the current time with the vested amount as its initial unlock
+        /// Only linear, quadratic and square-root curves can be extended; stepped, periodic
+        /// and monthly unlock dates would move, so they fail with `UnsupportedKind`
+        /// `new_end_time` - New end (configured unit); must be after the current end and
+        /// keep the grant within the maximum duration, counted from its current start
+        #[ink(message)]
+        pub fn extend_vesting(&mut self, beneficiary: H160, new_end_time: u64) -> Result<()> {
+            self.ensure_owner()?;
+            let mut schedule = self
+                .schedules
+                .get(beneficiary)
+                .ok_or(Error::NoVestingSchedule)?;
+            let new_end_time = self.to_millis(new_end_time);
+            if new_end_time <= schedule.end_time {
+                return Err(Error::InvalidTimeRange);
+            }
+            self.check_max_duration(schedule.start_time, new_end_time)?;
+            if schedule.revoked {
+                return Err(Error::AlreadyRevoked);
+            }
+            Self::ensure_continuous(&schedule)?;
+            // Re-anchoring would drop the frozen accrual; resume first
+            if schedule.suspended_at.is_some() {
+                return Err(Error::ScheduleSuspended);
+            }
+
+            let now = self.now();
+            if now > schedule.start_time {
+                schedule.initial_unlock = self.calculate_vested_amount(&schedule, now)?;
+                schedule.start_time = now;
+            }
+            let old_end_time = schedule.end_time;
+            schedule.end_time = new_end_time;
+            self.schedules.insert(beneficiary, &schedule);
+            self.record_admin_action(AdminActionKind::ExtendSchedule, beneficiary);
+            self.record_time_change(
+                beneficiary,
+                old_end_time,
+                new_end_time,
+                TimeChangeReason::Extended,
+            );
+            Ok(())
+        }
+
+        /// Adds tokens to an existing grant, vesting them over its remaining window (owner only)
+        /// Like `extend_vesting`, a running schedule is re-anchored at the current time with
+        /// the vested amount as its initial unlock, so nothing added vests retroactively;
+        /// running stepped, periodic and monthly schedules fail with `UnsupportedKind`
+        /// `additional` - Tokens added to `total_amount`
+        #[ink(message)]
+        pub fn top_up_vesting(&mut self, beneficiary: H160, additional: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            let mut schedule = self
+                .schedules
+                .get(beneficiary)
+                .ok_or(Error::NoVestingSchedule)?;
+            if additional == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            if schedule.revoked {
+                return Err(Error::AlreadyRevoked);
+            }
+            if schedule.suspended_at.is_some() {
+                return Err(Error::ScheduleSuspended);
+            }
+            // An ended schedule has no window left to vest over
+            let now = self.now();
+            if now >= schedule.end_time {
+                return Err(Error::InvalidTimeRange);
+            }
+
+            if now > schedule.start_time {
+                Self::ensure_continuous(&schedule)?;
+                schedule.initial_unlock = self.calculate_vested_amount(&schedule, now)?;
+                schedule.start_time = now;
+            }
+            schedule.total_amount = schedule
+                .total_amount
+                .checked_add(additional)
+                .ok_or(Error::Overflow)?;
+            self.schedules.insert(beneficiary, &schedule);
+            self.total_vested_allocated = self.total_vested_allocated.saturating_add(additional);
+            self.record_admin_action(AdminActionKind::TopUpSchedule, beneficiary);
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(VestingToppedUp {
+                    beneficiary,
+                    additional,
+                    new_total: schedule.total_amount,
+                });
+            }
+            Ok(())
+        }
+
+        /// Deletes a schedule that hasn't started yet, releasing its whole allocation (owner only)
+        /// Like revoked tokens, the released allocation can be taken back with `withdraw_reclaimed`
+        /// Running grants go through `revoke_vesting`; irrevocable grants can't be cancelled
+        #[ink(message)]
+        pub fn cancel_pending_vesting(&mut self, beneficiary: H160) -> Result<()> {
+            self.ensure_owner()?;
+            let schedule = self
+                .schedules
+                .get(beneficiary)
+                .ok_or(Error::NoVestingSchedule)?;
+            if self.now() >= schedule.start_time {
+                return Err(Error::VestingAlreadyStarted);
+            }
+            if !schedule.revocable {
+                return Err(Error::NotRevocable);
+            }
+
+            self.schedules.remove(beneficiary);
+            self.remove_beneficiary(beneficiary);
+            self.claim_history.remove(beneficiary);
+            self.last_claim_time.remove(beneficiary);
+            let unclaimed = schedule
+                .total_amount
+                .saturating_sub(schedule.claimed_amount);
+            self.total_vested_allocated = self.total_vested_allocated.saturating_sub(unclaimed);
+            self.reclaimable = self.reclaimable.saturating_add(unclaimed);
+            self.record_admin_action(AdminActionKind::CancelSchedule, beneficiary);
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(VestingCancelled {
+                    beneficiary,
+                    total_amount: unclaimed,
+                });
+            }
+            Ok(())
+        }
+
+        /// Cancels the unvested part of a schedule (owner only)
+        /// Tokens vested so far stay claimable; the schedule ends now
+        #[ink(message)]
+        pub fn revoke_vesting(&mut self, beneficiary: H160) -> Result<()> {
+            self.ensure_owner()?;
+            self.revoke(beneficiary)
+        }
+
+        /// Revokes several schedules at once (owner only)
+        /// Addresses without a schedule, or whose grant is irrevocable or already
+        /// revoked, are skipped; returns how many schedules were revoked
+        #[ink(message)]
+        pub fn revoke_vesting_batch(&mut self, beneficiaries: Vec<H160>) -> Result<u32> {
+            self.ensure_owner()?;
+            let mut revoked = 0u32;
+            for beneficiary in beneficiaries {
+                if self.revoke(beneficiary).is_ok() {
+                    revoked = revoked.saturating_add(1);
+                }
+            }
+            Ok(revoked)
+        }
+
+        // Freezes a schedule at its vested amount and books the rest as reclaimable
+        fn revoke(&mut self, beneficiary: H160) -> Result<()> {
+            let mut schedule = self
+                .schedules
+                .get(beneficiary)
+                .ok_or(Error::NoVestingSchedule)?;
+            if !schedule.revocable {
+                return Err(Error::NotRevocable);
+            }
+            if schedule.revoked {
+                return Err(Error::AlreadyRevoked);
+            }
+
+            let now = self.now();
+            let vested_amount = self.calculate_vested_amount(&schedule, now)?;
+            let unvested_amount = schedule.total_amount.saturating_sub(vested_amount);
+
+            // Freeze the schedule at what has vested
+            schedule.total_amount = vested_amount;
+            schedule.initial_unlock = schedule.initial_unlock.min(vested_amount);
+            let old_end_time = schedule.end_time;
+            schedule.end_time = now.clamp(schedule.start_time, schedule.end_time);
+            schedule.revoked = true;
+            // A revoked schedule is frozen for good
+            schedule.suspended_at = None;
+            debug_assert!(Self::invariants_hold(&schedule));
+            self.schedules.insert(beneficiary, &schedule);
+            self.total_vested_allocated =
+                self.total_vested_allocated.saturating_sub(unvested_amount);
+            self.reclaimable = self.reclaimable.saturating_add(unvested_amount);
+
+            self.record_admin_action(AdminActionKind::RevokeSchedule, beneficiary);
+            self.record_time_change(
+                beneficiary,
+                old_end_time,
+                schedule.end_time,
+                TimeChangeReason::Revoked,
+            );
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(VestingRevoked {
+                    beneficiary,
+                    vested_amount,
+                    unvested_amount,
+                });
+            }
+            Ok(())
+        }
+
+        /// Transfers all tokens released by revocations and cancellations to the owner (owner only)
+        #[ink(message)]
+        pub fn withdraw_reclaimed(&mut self) -> Result<Balance> {
+            self.ensure_owner()?;
+            let amount = self.reclaimable;
+            if amount == 0 {
+                return Err(Error::NoTokensAvailable);
+            }
+            self.reclaimable = 0;
+            self.total_withdrawn = self.total_withdrawn.saturating_add(amount);
+            self.env()
+                .transfer(self.owner, U256::from(amount))
+                .map_err(|_| Error::TransferFailed)?;
+            self.record_admin_action(AdminActionKind::WithdrawReclaimed, self.owner);
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(ReclaimedWithdrawn {
+                    recipient: self.owner,
+                    amount,
+                });
+            }
+            Ok(amount)
+        }
+
+        /// Transfers any balance above what the contract owes to the owner (owner only)
+        /// Unclaimed grants, claimed tokens, unwithdrawn fees, reclaimable revocations and
+        /// deposits made through `fund()` are never touched; returns the amount swept
+        #[ink(message)]
+        pub fn sweep_untracked(&mut self) -> Result<Balance> {
+            self.ensure_owner()?;
+            let surplus = self.contract_balance().saturating_sub(self.obligations());
+            if surplus == 0 {
+                return Err(Error::NoTokensAvailable);
+            }
+            self.env()
+                .transfer(self.owner, U256::from(surplus))
+                .map_err(|_| Error::TransferFailed)?;
+            self.record_admin_action(AdminActionKind::SweepUntracked, self.owner);
+            Ok(surplus)
+        }
+
+        /// Get the revoked and cancelled tokens waiting to be withdrawn by the owner
+        #[ink(message)]
+        pub fn get_reclaimable(&self) -> Balance {
+            self.reclaimable
+        }
+
+        /// Pauses accrual of a schedule without revoking it (owner only)
+        /// Tokens vested before the suspension stay claimable
+        #[ink(message)]
+        pub fn suspend_vesting(&mut self, beneficiary: H160) -> Result<()> {
+            self.ensure_owner()?;
+            let mut schedule = self
+                .schedules
+                .get(beneficiary)
+                .ok_or(Error::NoVestingSchedule)?;
+            if schedule.revoked {
+                return Err(Error::AlreadyRevoked);
+            }
+            if schedule.suspended_at.is_some() {
+                return Err(Error::ScheduleSuspended);
+            }
+            let now = self.now();
+            schedule.suspended_at = Some(now);
+            self.schedules.insert(beneficiary, &schedule);
+            self.record_admin_action(AdminActionKind::SuspendSchedule, beneficiary);
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(VestingSuspended {
+                    beneficiary,
+                    suspended_at: now,
+                });
+            }
+            Ok(())
+        }
+
+        /// Resumes a suspended schedule (owner only)
+        /// The schedule is shifted later by the time it spent suspended after its start,
+        /// so accrual picks up where it stopped and the full total still vests
+        #[ink(message)]
+        pub fn resume_vesting(&mut self, beneficiary: H160) -> Result<()> {
+            self.ensure_owner()?;
+            let mut schedule = self
+                .schedules
+                .get(beneficiary)
+                .ok_or(Error::NoVestingSchedule)?;
+            let suspended_at = schedule.suspended_at.ok_or(Error::NotSuspended)?;
+
+            // Time spent suspended before the start didn't cost any accrual
+            let paused_ms = self
+                .now()
+                .saturating_sub(suspended_at.max(schedule.start_time));
+            let old_end_time = schedule.end_time;
+            schedule.start_time = schedule.start_time.saturating_add(paused_ms);
+            schedule.end_time = schedule.end_time.saturating_add(paused_ms);
+            schedule.suspended_at = None;
+            self.schedules.insert(beneficiary, &schedule);
+            self.record_admin_action(AdminActionKind::ResumeSchedule, beneficiary);
+            self.record_time_change(
+                beneficiary,
+                old_end_time,
+                schedule.end_time,
+                TimeChangeReason::Resumed,
+            );
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(VestingResumed {
+                    beneficiary,
+                    paused_ms,
+                    end_time: schedule.end_time,
+                });
+            }
+            Ok(())
+        }
+
+        /// Permanently waives the right to revoke an existing schedule (owner only)
+        /// Grants that must never be revocable should be created with
+        /// `ScheduleParams::revocable` unset instead; this cannot be undone
+        #[ink(message)]
+        pub fn make_irrevocable(&mut self, beneficiary: H160) -> Result<()> {
+            self.ensure_owner()?;
+            let mut schedule = self
+                .schedules
+                .get(beneficiary)
+                .ok_or(Error::NoVestingSchedule)?;
+            schedule.revocable = false;
+            self.schedules.insert(beneficiary, &schedule);
+            self.record_admin_action(AdminActionKind::MakeIrrevocable, beneficiary);
+            Ok(())
+        }
+
+        /// Permanently gives up ownership; every owner-only message fails afterwards
+        #[ink(message)]
+        pub fn renounce_ownership(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            let previous_owner = self.owner;
+            self.owner = H160::zero();
+            self.record_admin_action(AdminActionKind::RenounceOwnership, previous_owner);
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(OwnershipTransferred {
+                    previous_owner,
+                    new_owner: H160::zero(),
+                });
+            }
+            Ok(())
+        }
+
+        /// Sets how far back (in ms) a schedule's start time may be (owner only)
+        /// `ms` - Maximum backdate window; 0 disables the check
+        #[ink(message)]
+        pub fn set_max_backdate(&mut self, ms: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_backdate_ms = ms;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Sets the longest schedule that can be created (owner only)
+        /// `ms` - Maximum `end_time - start_time` in milliseconds; 0 means unlimited
+        #[ink(message)]
+        pub fn set_max_duration(&mut self, ms: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_duration_ms = ms;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Sets the shortest schedule that can be created (owner only)
+        /// `ms` - Minimum `end_time - start_time` in milliseconds; 0 disables the check
+        #[ink(message)]
+        pub fn set_min_duration(&mut self, ms: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.min_duration_ms = ms;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Sets how vested amounts are rounded (owner only)
+        /// Applies to every schedule created without its own mode; the vested amount
+        /// is always capped at the schedule's total
+        #[ink(message)]
+        pub fn set_rounding_mode(&mut self, mode: RoundingMode) -> Result<()> {
+            self.ensure_owner()?;
+            self.rounding_mode = mode;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Sets how many claims are kept per beneficiary (owner only)
+        /// Older entries are dropped on the next claim; 0 disables the history
+        #[ink(message)]
+        pub fn set_max_claim_history(&mut self, len: u32) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_claim_history = len;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Sets the minimum time between two claims of the same beneficiary (owner only)
+        /// `ms` - Cooldown in milliseconds; 0 disables it
+        #[ink(message)]
+        pub fn set_min_claim_interval(&mut self, ms: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.min_claim_interval_ms = ms;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Sets how long after its end a schedule is protected from pruning (owner only)
+        /// `ms` - Grace period in milliseconds; 0 disables it
+        #[ink(message)]
+        pub fn set_prune_grace(&mut self, ms: u64) -> Result<()> {
+            self.ensure_owner()?;
+            self.prune_grace_ms = ms;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Allows creating schedules that have already ended, e.g. to backfill history (owner only)
+        /// Off by default: such grants are fully vested at once
+        #[ink(message)]
+        pub fn set_allow_past_end(&mut self, allowed: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.allow_past_end = allowed;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Caps how many addresses may hold a schedule, keeping the enumeration views
+        /// usable (owner only)
+        /// `max` - Maximum number of beneficiaries; 0 means unlimited
+        #[ink(message)]
+        pub fn set_max_beneficiaries(&mut self, max: u32) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_beneficiaries = max;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Get the beneficiary cap (0 = unlimited)
+        #[ink(message)]
+        pub fn get_max_beneficiaries(&self) -> u32 {
+            self.max_beneficiaries
+        }
+
+        /// Sets which events the contract emits (owner only)
+        /// `None` saves the most gas; `Verbose` adds the readable events and `AdminAction` audit logs
+        #[ink(message)]
+        pub fn set_event_verbosity(&mut self, verbosity: EventVerbosity) -> Result<()> {
+            self.ensure_owner()?;
+            self.event_verbosity = verbosity;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Get which events the contract emits
+        #[ink(message)]
+        pub fn get_event_verbosity(&self) -> EventVerbosity {
+            self.event_verbosity
+        }
+
+        /// Sets the fee skimmed from each claim in basis points (owner only)
+        /// The fee is credited to the fee recipient, who collects it with `withdraw_fees`
+        /// `bps` - Fee in basis points, at most 10000 (100%)
+        #[ink(message)]
+        pub fn set_claim_fee_bps(&mut self, bps: u16) -> Result<()> {
+            self.ensure_owner()?;
+            if bps > MAX_BPS {
+                return Err(Error::InvalidFee);
+            }
+            self.claim_fee_bps = bps;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Sets the account credited with claim fees (owner only)
+        #[ink(message)]
+        pub fn set_fee_recipient(&mut self, recipient: H160) -> Result<()> {
+            self.ensure_owner()?;
+            // Fees credited to the zero address would be burned
+            if recipient.is_zero() {
+                return Err(Error::InvalidBeneficiary);
+            }
+            self.fee_recipient = recipient;
+            self.record_admin_action(AdminActionKind::UpdateConfig, recipient);
+            Ok(())
+        }
+
+        /// Sets the fee recipient and rate together, so no claim sees half the change (owner only)
+        /// `recipient` - Account credited with fees, must not be the zero address
+        /// `bps` - Fee in basis points, at most 10000 (100%)
+        #[ink(message)]
+        pub fn set_fee_config(&mut self, recipient: H160, bps: u16) -> Result<()> {
+            self.ensure_owner()?;
+            if bps > MAX_BPS {
+                return Err(Error::InvalidFee);
+            }
+            // Fees credited to the zero address would be burned
+            if recipient.is_zero() {
+                return Err(Error::InvalidBeneficiary);
+            }
+            self.fee_recipient = recipient;
+            self.claim_fee_bps = bps;
+            self.record_admin_action(AdminActionKind::UpdateConfig, recipient);
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(FeeConfigUpdated { recipient, bps });
+            }
+            Ok(())
+        }
+
+        /// Sets the smallest fee charged on any claim, in token units (owner only)
+        /// The fee never exceeds the claimed amount; 0 disables the floor
+        #[ink(message)]
+        pub fn set_min_fee(&mut self, min_fee: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            self.min_fee = min_fee;
+            self.record_admin_action(AdminActionKind::UpdateConfig, H160::zero());
+            Ok(())
+        }
+
+        /// Get the smallest fee charged on any claim
+        #[ink(message)]
+        pub fn get_min_fee(&self) -> Balance {
+            self.min_fee
+        }
+
+        /// Get every error as (code, zero-padded name), in code order
+        /// This is the wire contract for off-chain error handling
+        #[ink(message)]
+        pub fn error_code_table(&self) -> Vec<(u16, [u8; 32])> {
+            Error::ALL
+                .iter()
+                .map(|error| {
+                    let mut name = [0u8; 32];
+                    let bytes = error.name().as_bytes();
+                    name[..bytes.len()].copy_from_slice(bytes);
+                    (error.code(), name)
+                })
+                .collect()
+        }
+
+        /// Get the contract owner (the zero address once ownership is renounced)
+        #[ink(message)]
+        pub fn get_owner(&self) -> H160 {
+            self.owner
+        }
+
+        /// Get the claim fee in basis points
+        #[ink(message)]
+        pub fn get_claim_fee_bps(&self) -> u16 {
+            self.claim_fee_bps
+        }
+
+        /// Get the account credited with claim fees
+        #[ink(message)]
+        pub fn get_fee_recipient(&self) -> H160 {
+            self.fee_recipient
+        }
+
+        /// Transfers the claim fees credited to the caller, e.g. the treasury
+        /// Fees stay with the account that was fee recipient when they were collected
+        #[ink(message)]
+        pub fn withdraw_fees(&mut self) -> Result<Balance> {
+            let caller = self.env().caller();
+            let amount = self.fee_balances.get(caller).unwrap_or(0);
+            if amount == 0 {
+                return Err(Error::NoTokensAvailable);
+            }
+            self.fee_balances.remove(caller);
+            self.unpaid_fees = self.unpaid_fees.saturating_sub(amount);
+            self.total_withdrawn = self.total_withdrawn.saturating_add(amount);
+            self.env()
+                .transfer(caller, U256::from(amount))
+                .map_err(|_| Error::TransferFailed)?;
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(FeesWithdrawn {
+                    recipient: caller,
+                    amount,
+                });
+            }
+            Ok(amount)
+        }
+
+        /// Get the claim fees an account can withdraw
+        #[ink(message)]
+        pub fn get_fee_balance(&self, account: H160) -> Balance {
+            self.fee_balances.get(account).unwrap_or(0)
+        }
+
+        /// Deposits the transferred value to back the schedules; anyone may fund
+        /// Returns the cumulative amount funded
+        #[ink(message, payable)]
+        pub fn fund(&mut self) -> Result<Balance> {
+            let amount =
+                Balance::try_from(self.env().transferred_value()).map_err(|_| Error::Overflow)?;
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            self.total_funded = self
+                .total_funded
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(FundsReceived {
+                    from: self.env().caller(),
+                    amount,
+                    total_funded: self.total_funded,
+                });
+            }
+            Ok(self.total_funded)
+        }
+
+        /// Get the cumulative amount received through `fund()`
+        #[ink(message)]
+        pub fn get_total_funded(&self) -> Balance {
+            self.total_funded
+        }
+
+        /// Claims vested tokens, net of the claim fee
+        /// The claim is recorded in the ledger (`TokensClaimed`); no value is transferred
+        /// Returns the amount credited to the beneficiary; fails with `TimeNotAvailable`
+        /// while the block timestamp is 0 (e.g. at genesis)
+        #[ink(message)]
+        pub fn claim_vested(&mut self) -> Result<Balance> {
+            let caller = self.env().caller();
+            self.claim_guarded(caller, caller, Balance::MAX)
+        }
+
+        /// Claims vested tokens like `claim_vested`, also returning what is left unclaimed
+        /// Returns (amount credited this call, remaining unclaimed balance)
+        #[ink(message)]
+        pub fn claim_vested_detailed(&mut self) -> Result<(Balance, Balance)> {
+            let claimed = self.claim_vested()?;
+            let remaining = self
+                .remaining_balance(self.env().caller())
+                .unwrap_or_default();
+            Ok((claimed, remaining))
+        }
+
+        /// Claims the caller's vested tokens to another account, net of the claim fee
+        /// `recipient` - Account credited with the tokens (e.g. a cold wallet); must be
+        /// whitelisted if the owner restricted the caller's recipients
+        #[ink(message)]
+        pub fn claim_vested_to(&mut self, recipient: H160) -> Result<Balance> {
+            let caller = self.env().caller();
+            if !self.is_recipient_allowed(caller, recipient) {
+                return Err(Error::RecipientNotAllowed);
+            }
+            self.claim_guarded(caller, recipient, Balance::MAX)
+        }
+
+        /// Whitelists a claim recipient for a beneficiary (owner only)
+        /// The first entry restricts the beneficiary's redirected claims to the whitelist
+        /// for good; claims to the beneficiary itself are always allowed
+        #[ink(message)]
+        pub fn add_allowed_recipient(&mut self, beneficiary: H160, recipient: H160) -> Result<()> {
+            self.ensure_owner()?;
+            self.restricted_recipients.insert(beneficiary, &());
+            self.allowed_recipients
+                .insert((beneficiary, recipient), &true);
+            self.record_admin_action(AdminActionKind::UpdateAllowedRecipients, beneficiary);
+            Ok(())
+        }
+
+        /// Removes a claim recipient from a beneficiary's whitelist (owner only)
+        /// The restriction stays on even once the whitelist is empty
+        #[ink(message)]
+        pub fn remove_allowed_recipient(
+            &mut self,
+            beneficiary: H160,
+            recipient: H160,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            self.allowed_recipients.remove((beneficiary, recipient));
+            self.record_admin_action(AdminActionKind::UpdateAllowedRecipients, beneficiary);
+            Ok(())
+        }
+
+        /// Check whether a beneficiary may claim to `recipient`
+        #[ink(message)]
+        pub fn is_recipient_allowed(&self, beneficiary: H160, recipient: H160) -> bool {
+            recipient == beneficiary
+                || !self.restricted_recipients.contains(beneficiary)
+                || self
+                    .allowed_recipients
+                    .get((beneficiary, recipient))
+                    .unwrap_or(false)
+        }
+
+        /// Claims like `claim_vested`, tagging the claim with `memo` in a
+        /// `TokensClaimedWithMemo` event for reconciliation
+        #[ink(message)]
+        pub fn claim_vested_with_memo(&mut self, memo: [u8; 32]) -> Result<Balance> {
+            let caller = self.env().caller();
+            let amount = self.claim_guarded(caller, caller, Balance::MAX)?;
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(TokensClaimedWithMemo {
+                    beneficiary: caller,
+                    memo,
+                    amount,
+                });
+            }
+            Ok(amount)
+        }
+
+        /// Claims at most `max_claim` vested tokens (before fees), leaving the rest claimable
+        /// Returns the amount credited to the beneficiary
+        #[ink(message)]
+        pub fn claim_vested_capped(&mut self, max_claim: Balance) -> Result<Balance> {
+            let caller = self.env().caller();
+            self.claim_guarded(caller, caller, max_claim)
+        }
+
+        /// Claims only if exactly `expected` tokens (before fees) are claimable,
+        /// guarding against a stale UI quote or a front-run state change
+        #[ink(message)]
+        pub fn claim_vested_exact(&mut self, expected: Balance) -> Result<Balance> {
+            let caller = self.env().caller();
+            if self.claimable_at(caller, self.now())? != expected {
+                return Err(Error::SlippageExceeded);
+            }
+            self.claim_guarded(caller, caller, expected)
+        }
+
+        /// Approves an operator (e.g. a keeper bot) to claim on the caller's behalf
+        /// `operator` - Account allowed to call `claim_vested_for` for the caller
+        #[ink(message)]
+        pub fn set_operator(&mut self, operator: H160) {
+            let caller = self.env().caller();
+            self.operators.insert(caller, &operator);
+        }
+
+        /// Get the operator approved by a beneficiary
+        #[ink(message)]
+        pub fn get_operator(&self, beneficiary: H160) -> Option<H160> {
+            self.operators.get(beneficiary)
+        }
+
+        /// Claims vested tokens for a beneficiary; tokens are still credited to the beneficiary
+        /// Callable by the beneficiary or their approved operator
+        #[ink(message)]
+        pub fn claim_vested_for(&mut self, beneficiary: H160) -> Result<Balance> {
+            let caller = self.env().caller();
+            if caller != beneficiary && self.operators.get(beneficiary) != Some(caller) {
+                return Err(Error::UnauthorizedOperator);
+            }
+            self.claim_guarded(beneficiary, beneficiary, Balance::MAX)
+        }
+
+        /// Claims for `beneficiary` using their off-chain signature, so a relayer pays the gas
+        /// The beneficiary signs keccak256 of SCALE-encoded (beneficiary, nonce, contract address)
+        /// `nonce` - Must equal `get_nonce(beneficiary)`; it is consumed on success
+        /// `signature` - 65-byte recoverable ECDSA (secp256k1) signature
+        #[ink(message)]
+        pub fn claim_vested_meta(
+            &mut self,
+            beneficiary: H160,
+            nonce: u64,
+            signature: [u8; 65],
+        ) -> Result<Balance> {
+            if nonce != self.nonces.get(beneficiary).unwrap_or(0) {
+                return Err(Error::BadNonce);
+            }
+            let message_hash = self.meta_claim_hash(beneficiary, nonce);
+            if self.recover_signer(&signature, &message_hash)? != beneficiary {
+                return Err(Error::InvalidSignature);
+            }
+            // Consume the nonce so the signature can't be replayed
+            self.nonces.insert(beneficiary, &nonce.saturating_add(1));
+            self.claim_guarded(beneficiary, beneficiary, Balance::MAX)
+        }
+
+        /// Get the nonce the beneficiary's next signed claim must use
+        #[ink(message)]
+        pub fn get_nonce(&self, beneficiary: H160) -> u64 {
+            self.nonces.get(beneficiary).unwrap_or(0)
+        }
+
+        // Runs a claim under the reentrancy lock
+        fn claim_guarded(
+            &mut self,
+            beneficiary: H160,
+            recipient: H160,
+            max_claim: Balance,
+        ) -> Result<Balance> {
+            if self.locked {
+                return Err(Error::Reentrancy);
+            }
+            self.locked = true;
+            // The lock is released on every path, including errors
+            let result = self.settle_claim(beneficiary, recipient, max_claim);
+            self.locked = false;
+            result
+        }
+
+        // Settles a claim for `beneficiary` to `recipient` (checks, then state updates, then events).
+        // Claims are ledger entries: the payout is credited by events and stays held in
+        // `claimed_unpaid`, the fee goes to the recipient's withdrawable `fee_balances`.
+        // At most `max_claim` (before fees) leaves the schedule; the rest stays claimable.
+        fn settle_claim(
+            &mut self,
+            beneficiary: H160,
+            recipient: H160,
+            max_claim: Balance,
+        ) -> Result<Balance> {
+            let current_time = self.now();
+            // A zero clock would otherwise surface as a misleading `VestingNotStarted`
+            if current_time == 0 {
+                return Err(Error::TimeNotAvailable);
+            }
+
+            // Retrieve the vesting schedule
+            let mut schedule = self
+                .schedules
+                .get(beneficiary)
+                .ok_or(Error::NoVestingSchedule)?;
+
+            // Confirm that vesting has started
+            if current_time < schedule.start_time {
+                return Err(Error::VestingNotStarted);
+            }
+
+            // Enforce the cooldown between claims
+            if self.min_claim_interval_ms > 0
+                && let Some(last_claim) = self.last_claim_time.get(beneficiary)
+                && current_time.saturating_sub(last_claim) < self.min_claim_interval_ms
+            {
+                return Err(Error::ClaimTooSoon);
+            }
+
+            // Calculate vested amount
+            let vested_amount = self.calculate_vested_amount(&schedule, current_time)?;
+            let claimable = vested_amount
+                .saturating_sub(schedule.claimed_amount)
+                .min(max_claim);
+
+            if claimable == 0 {
+                return Err(Error::NoTokensAvailable);
+            }
+
+            // Split off the fee: claimable * bps / 10000 goes to the fee recipient,
+            // but never less than the fee floor so drip claims can't dodge it
+            let fee = (claimable.saturating_mul(self.claim_fee_bps as u128) / MAX_BPS as u128)
+                .max(self.min_fee)
+                .min(claimable);
+            let payout = claimable.saturating_sub(fee);
+
+            // Update claimed amount (the fee is part of what left the schedule)
+            schedule.claimed_amount = schedule.claimed_amount.saturating_add(claimable);
+            debug_assert!(Self::invariants_hold(&schedule));
+            self.total_claimed = self.total_claimed.saturating_add(claimable);
+            self.claimed_unpaid = self.claimed_unpaid.saturating_add(payout);
+            if fee > 0 {
+                let fee_balance = self.fee_balances.get(self.fee_recipient).unwrap_or(0);
+                self.fee_balances
+                    .insert(self.fee_recipient, &fee_balance.saturating_add(fee));
+                self.unpaid_fees = self.unpaid_fees.saturating_add(fee);
+            }
+            self.schedules.insert(beneficiary, &schedule);
+            self.last_claim_time.insert(beneficiary, &current_time);
+            let count = self.claim_count.get(beneficiary).unwrap_or(0);
+            self.claim_count
+                .insert(beneficiary, &count.saturating_add(1));
+            let lifetime = self.lifetime_claimed.get(beneficiary).unwrap_or(0);
+            self.lifetime_claimed
+                .insert(beneficiary, &lifetime.saturating_add(claimable));
+            self.record_claim(beneficiary, current_time, payout);
+
+            // Emit event(standard event)
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(TokensClaimed {
+                    beneficiary,
+                    recipient,
+                    amount: payout,
+                    amount_bucket: Self::amount_bucket(payout),
+                    claimed_at: current_time,
+                });
+            }
+            // Emit event with readable timestamp (demonstrates on-chain conversion)
+            if self.emits(EventVerbosity::Verbose) {
+                let dt = self.timestamp_to_datetime(current_time);
+                self.env().emit_event(TokensClaimedReadable {
+                    beneficiary,
+                    amount: payout,
+                    claimed_at: current_time,
+                    claimed_at_readable: self.format_datetime_millis(dt),
+                    amount_readable: self.format_amount(payout).0,
+                });
+            }
+            if fee > 0 && self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(FeeCollected {
+                    beneficiary,
+                    recipient: self.fee_recipient,
+                    amount: fee,
+                });
+            }
+
+            Ok(payout)
+        }
+
+        /// Moves the caller's schedule (including claimed progress) to a new address
+        /// `new_beneficiary` - Address that takes over the grant; must not be the zero address
+        /// or already have a schedule
+        #[ink(message)]
+        pub fn transfer_schedule(&mut self, new_beneficiary: H160) -> Result<()> {
+            let caller = self.env().caller();
+            self.move_schedule(caller, new_beneficiary)?;
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(ScheduleTransferred {
+                    from: caller,
+                    to: new_beneficiary,
+                });
+            }
+            Ok(())
+        }
+
+        /// Redirects a grant to a new beneficiary, e.g. an estate (owner only)
+        /// Claimed progress, claim history and cooldown move along with the schedule
+        /// Irrevocable grants can't be reassigned, as that would let the owner take them back
+        /// `to` - Address that takes over the grant; must not have a schedule
+        #[ink(message)]
+        pub fn reassign_beneficiary(&mut self, from: H160, to: H160) -> Result<()> {
+            self.ensure_owner()?;
+            let schedule = self.schedules.get(from).ok_or(Error::NoVestingSchedule)?;
+            if !schedule.revocable {
+                return Err(Error::NotRevocable);
+            }
+            self.move_schedule(from, to)?;
+            self.record_admin_action(AdminActionKind::ReassignBeneficiary, to);
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(BeneficiaryReassigned { from, to });
+            }
+            Ok(())
+        }
+
+        /// Deletes a fully claimed schedule to free its storage (owner or beneficiary)
+        /// Fails with `PruneTooSoon` within the prune grace period after the schedule's end
+        /// Claim nonces are kept so old signatures can't be replayed
+        #[ink(message)]
+        pub fn prune_schedule(&mut self, beneficiary: H160) -> Result<()> {
+            let caller = self.env().caller();
+            let by_owner = caller != beneficiary;
+            if by_owner {
+                self.ensure_owner()?;
+            }
+            let schedule = self
+                .schedules
+                .get(beneficiary)
+                .ok_or(Error::NoVestingSchedule)?;
+            if schedule.claimed_amount != schedule.total_amount {
+                return Err(Error::NotFullyClaimed);
+            }
+            // Leave recently ended grants alone so late claimers can still look them up
+            if self.now() < schedule.end_time.saturating_add(self.prune_grace_ms) {
+                return Err(Error::PruneTooSoon);
+            }
+
+            self.schedules.remove(beneficiary);
+            self.remove_beneficiary(beneficiary);
+            self.claim_history.remove(beneficiary);
+            self.last_claim_time.remove(beneficiary);
+            if by_owner {
+                self.record_admin_action(AdminActionKind::PruneSchedule, beneficiary);
+            }
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(SchedulePruned { beneficiary });
+            }
+            Ok(())
+        }
+
+        /// View function to get vesting schedule with readable dates
+        /// Returns `None` if there is no schedule or a date lies beyond year 9999
+        /// With private schedules, only the owner and the beneficiary get a result
+        #[ink(message)]
+        pub fn get_vesting_schedule_readable(&self, beneficiary: H160) -> Option<ReadableSchedule> {
+            let schedule = self.get_vesting_schedule(beneficiary)?;
+
+            let start_dt = self.timestamp_to_datetime_checked(schedule.start_time)?;
+            let end_dt = self.timestamp_to_datetime_checked(schedule.end_time)?;
+
+            Some((
+                schedule,
+                self.format_datetime(start_dt),
+                self.format_datetime(end_dt),
+            ))
+        }
+
+        /// `get_vesting_schedule_readable` for many addresses at once, in input order
+        /// Only the first 200 addresses are served
+        #[ink(message)]
+        pub fn get_schedules_readable_batch(
+            &self,
+            beneficiaries: Vec<H160>,
+        ) -> Vec<Option<ReadableSchedule>> {
+            beneficiaries
+                .iter()
+                .take(MAX_BATCH_SIZE as usize)
+                .map(|beneficiary| self.get_vesting_schedule_readable(*beneficiary))
+                .collect()
+        }
+
+        /// Get vesting schedule (raw timestamps only)
+        /// With private schedules, only the owner and the beneficiary get a result
+        #[ink(message)]
+        pub fn get_vesting_schedule(&self, beneficiary: H160) -> Option<VestingSchedule> {
+            if !self.can_view(beneficiary) {
+                return None;
+            }
+            self.schedules.get(beneficiary)
+        }
+
+        /// Get the caller's own vesting schedule
+        #[ink(message)]
+        pub fn get_my_schedule(&self) -> Option<VestingSchedule> {
+            self.schedules.get(self.env().caller())
+        }
+
+        /// Get how much of a grant is still unclaimed (`total_amount - claimed_amount`)
+        #[ink(message)]
+        pub fn remaining_balance(&self, beneficiary: H160) -> Option<Balance> {
+            let schedule = self.get_vesting_schedule(beneficiary)?;
+            Some(
+                schedule
+                    .total_amount
+                    .saturating_sub(schedule.claimed_amount),
+            )
+        }
+
+        /// Get the most recent claims of a beneficiary as (timestamp, amount), oldest first
+        #[ink(message)]
+        pub fn get_claim_history(&self, beneficiary: H160) -> Vec<(u64, Balance)> {
+            if !self.can_view(beneficiary) {
+                return Vec::new();
+            }
+            self.claim_history.get(beneficiary).unwrap_or_default()
+        }
+
+        /// Get how many successful claims a beneficiary has made
+        #[ink(message)]
+        pub fn get_claim_count(&self, beneficiary: H160) -> u32 {
+            if !self.can_view(beneficiary) {
+                return 0;
+            }
+            self.claim_count.get(beneficiary).unwrap_or(0)
+        }
+
+        /// Get everything a beneficiary has claimed (including fees) across all their grants
+        /// Unlike `claimed_amount`, this survives pruned schedules; it stays
+        /// with the claiming address when a schedule is transferred
+        #[ink(message)]
+        pub fn total_claimed_by(&self, beneficiary: H160) -> Balance {
+            if !self.can_view(beneficiary) {
+                return 0;
+            }
+            self.lifetime_claimed.get(beneficiary).unwrap_or(0)
+        }
+
+        /// Get every address holding a schedule, in creation order
+        #[ink(message)]
+        pub fn get_all_beneficiaries(&self) -> Vec<H160> {
+            self.iter_beneficiaries().collect()
+        }
+
+        /// Get a page of beneficiaries in creation order
+        /// `start` - Index of the first entry; out of range returns an empty page
+        /// `limit` - Page size, clamped to 100
+        #[ink(message)]
+        pub fn get_beneficiaries_paged(&self, start: u32, limit: u32) -> Vec<H160> {
+            let end = start
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.beneficiaries.len());
+            (start..end)
+                .filter_map(|index| self.beneficiaries.get(index))
+                .collect()
+        }
+
+        /// Get the total of all unclaimed tokens owed, i.e. what must stay funded
+        #[ink(message)]
+        pub fn total_outstanding(&self) -> Balance {
+            self.total_vested_allocated
+                .saturating_sub(self.total_claimed)
+        }
+
+        /// Health check comparing the native balance with what the contract owes
+        /// Returns `(actual_balance, expected_obligations, is_solvent)`, where obligations
+        /// are all unclaimed grants, claimed tokens still held, unwithdrawn fees and revoked
+        /// tokens awaiting withdrawal, or the deposits made through `fund()` if those are larger
+        #[ink(message)]
+        pub fn reconcile(&self) -> (Balance, Balance, bool) {
+            let actual_balance = self.contract_balance();
+            let expected_obligations = self.obligations();
+            (
+                actual_balance,
+                expected_obligations,
+                actual_balance >= expected_obligations,
+            )
+        }
+
+        /// Recomputes `total_vested_allocated` and `total_claimed` from the stored schedules,
+        /// repairing drift e.g. after a faulty migration (owner only)
+        /// Afterwards both counters cover live schedules only: amounts from pruned
+        /// grants drop out of both, so `total_outstanding` is exact.
+        /// Iterates every beneficiary, so gas grows with the number of grants
+        #[ink(message)]
+        pub fn resync_counters(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            let (allocated, claimed) = self
+                .iter_beneficiaries()
+                .filter_map(|beneficiary| self.schedules.get(beneficiary))
+                .fold((0 as Balance, 0 as Balance), |(allocated, claimed), s| {
+                    (
+                        allocated.saturating_add(s.total_amount),
+                        claimed.saturating_add(s.claimed_amount),
+                    )
+                });
+            let (old_allocated, old_claimed) = (self.total_vested_allocated, self.total_claimed);
+            self.total_vested_allocated = allocated;
+            self.total_claimed = claimed;
+            self.record_admin_action(AdminActionKind::ResyncCounters, H160::zero());
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(CountersResynced {
+                    old_total_allocated: old_allocated,
+                    new_total_allocated: allocated,
+                    old_total_claimed: old_claimed,
+                    new_total_claimed: claimed,
+                });
+            }
+            Ok(())
+        }
+
+        /// Get beneficiaries whose schedule ends within `[from, to]` (configured unit)
+        /// Iterates every beneficiary, so gas grows with the number of grants;
+        /// at most 100 matches are returned, in creation order
+        #[ink(message)]
+        pub fn beneficiaries_ending_between(&self, from: u64, to: u64) -> Vec<H160> {
+            let (from, to) = (self.to_millis(from), self.to_millis(to));
+            self.iter_beneficiaries()
+                .filter(|beneficiary| {
+                    self.get_vesting_schedule(*beneficiary)
+                        .is_some_and(|s| s.end_time >= from && s.end_time <= to)
+                })
+                .take(MAX_PAGE_SIZE as usize)
+                .collect()
+        }
+
+        /// Get beneficiaries with their end time (ms), earliest first, for timeline views
+        /// Loads and sorts every schedule, so gas grows with the number of grants;
+        /// at most the 100 earliest are returned, ties in creation order
+        #[ink(message)]
+        pub fn get_beneficiaries_by_end_time(&self) -> Vec<(H160, u64)> {
+            let mut ends: Vec<(H160, u64)> = self
+                .iter_beneficiaries()
+                .filter_map(|beneficiary| {
+                    self.get_vesting_schedule(beneficiary)
+                        .map(|s| (beneficiary, s.end_time))
+                })
+                .collect();
+            ends.sort_by_key(|(_, end_time)| *end_time);
+            ends.truncate(MAX_PAGE_SIZE as usize);
+            ends
+        }
+
+        /// Get beneficiaries whose remaining obligation isn't covered by the contract balance
+        /// Claimed tokens still held, unwithdrawn fees and revoked tokens are set aside
+        /// first, as in `reconcile`; the rest is assigned to schedules in creation order.
+        /// Iterates every beneficiary
+        #[ink(message)]
+        pub fn get_underfunded_beneficiaries(&self) -> Vec<H160> {
+            let mut available = self
+                .contract_balance()
+                .saturating_sub(self.claimed_unpaid)
+                .saturating_sub(self.unpaid_fees)
+                .saturating_sub(self.reclaimable);
+            let mut underfunded = Vec::new();
+            for beneficiary in self.iter_beneficiaries() {
+                let Some(schedule) = self.schedules.get(beneficiary) else {
+                    continue;
+                };
+                let remaining = schedule
+                    .total_amount
+                    .saturating_sub(schedule.claimed_amount);
+                if remaining > available && self.can_view(beneficiary) {
+                    underfunded.push(beneficiary);
+                }
+                available = available.saturating_sub(remaining);
+            }
+            underfunded
+        }
+
+        /// Get the decimal precision of the vested token
+        #[ink(message)]
+        pub fn get_decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        /// Render an amount with a decimal point, e.g. 1000000 with 6 decimals -> "1.000000"
+        /// Returns the buffer and the number of bytes used; decimals are capped at 38
+        #[ink(message)]
+        pub fn format_amount(&self, value: Balance) -> ([u8; 40], u8) {
+            let decimals = self.decimals.min(MAX_DECIMALS) as usize;
+
+            // Digits, least significant first
+            let mut digits = [b'0'; 39];
+            let mut count = 0;
+            let mut rest = value;
+            while rest > 0 {
+                digits[count] = b'0' + (rest % 10) as u8;
+                rest /= 10;
+                count += 1;
+            }
+            // Keep at least one digit before the decimal point
+            let count = count.max(decimals + 1);
+
+            let mut buf = [0u8; 40];
+            let mut len = 0;
+            for (i, digit) in digits[..count].iter().enumerate().rev() {
+                buf[len] = *digit;
+                len += 1;
+                if i == decimals && decimals > 0 {
+                    buf[len] = b'.';
+                    len += 1;
+                }
+            }
+            (buf, len as u8)
+        }
+
+        /// Get the raw block timestamp the contract sees (chain's unit)
+        #[ink(message)]
+        pub fn current_block_time(&self) -> u64 {
+            self.env().block_timestamp()
+        }
+
+        /// Get the current block time as "YYYY-MM-DD HH:MM:SS" (UTC)
+        /// Returns `None` if the clock lies beyond year 9999
+        #[ink(message)]
+        pub fn current_block_time_readable(&self) -> Option<[u8; 19]> {
+            let dt = self.timestamp_to_datetime_checked(self.now())?;
+            Some(self.format_datetime(dt))
+        }
+
+        /// Get the storage schema version of this deployment
+        #[ink(message)]
+        pub fn get_version(&self) -> u16 {
+            self.version
+        }
+
+        /// Get the magic marker identifying the storage layout
+        #[ink(message)]
+        pub fn storage_schema(&self) -> [u8; 4] {
+            STORAGE_SCHEMA
+        }
+
+        /// Get a canonical byte form of a schedule for bridges to prove against:
+        /// SCALE-encoded `(EXPORT_FORMAT_VERSION, beneficiary, schedule)`
+        /// With private schedules, only the owner and the beneficiary get a result
+        #[ink(message)]
+        pub fn export_schedule(&self, beneficiary: H160) -> Option<Vec<u8>> {
+            use ink::scale::Encode;
+            let schedule = self.get_vesting_schedule(beneficiary)?;
+            Some((EXPORT_FORMAT_VERSION, beneficiary, schedule).encode())
+        }
+
+        /// Get the length of a schedule in whole days (rounded down)
+        /// Saturates at `u32::MAX` for schedules too long to count in a u32
+        #[ink(message)]
+        pub fn schedule_duration_days(&self, beneficiary: H160) -> Option<u32> {
+            let schedule = self.get_vesting_schedule(beneficiary)?;
+            let duration = schedule.end_time.saturating_sub(schedule.start_time);
+            Some(u32::try_from(duration / MS_PER_DAY).unwrap_or(u32::MAX))
+        }
+
+        /// Get how far through its duration a schedule is, in basis points (0-10000)
+        /// Time-based, so it ignores the curve shape; clamped at 10000 after the end
+        #[ink(message)]
+        pub fn vesting_progress_bps(&self, beneficiary: H160) -> Option<u16> {
+            let schedule = self.get_vesting_schedule(beneficiary)?;
+            let now = Self::accrual_time(&schedule, self.now());
+            if now >= schedule.end_time {
+                return Some(MAX_BPS);
+            }
+            let duration = schedule.end_time.saturating_sub(schedule.start_time);
+            // Revoking before the start collapses the schedule to a single instant
+            if duration == 0 {
+                return Some(MAX_BPS);
+            }
+            let elapsed = now.saturating_sub(schedule.start_time);
+            Some(((elapsed as u128) * (MAX_BPS as u128) / (duration as u128)) as u16)
+        }
+
+        /// Check whether a schedule has reached its end time (regardless of what was claimed)
+        /// A suspended schedule stays where it was paused; `None` if there is no schedule
+        #[ink(message)]
+        pub fn is_fully_vested(&self, beneficiary: H160) -> Option<bool> {
+            let schedule = self.get_vesting_schedule(beneficiary)?;
+            Some(Self::accrual_time(&schedule, self.now()) >= schedule.end_time)
+        }
+
+        /// Get how fast a linear schedule vests, in tokens per ms scaled by `RATE_SCALE`
+        /// The initial unlock is excluded; multiply by 1000 for tokens per second
+        /// Returns `None` without a schedule or for non-linear curves
+        #[ink(message)]
+        pub fn vesting_rate_per_ms(&self, beneficiary: H160) -> Option<u128> {
+            let schedule = self.get_vesting_schedule(beneficiary)?;
+            if schedule.kind != VestingKind::Linear {
+                return None;
+            }
+            let amount = schedule
+                .total_amount
+                .saturating_sub(schedule.initial_unlock);
+            let duration = schedule.end_time.saturating_sub(schedule.start_time) as u128;
+            if duration == 0 {
+                return None;
+            }
+            // Split into whole and fractional parts so the scaling can't overflow
+            let whole = (amount / duration).saturating_mul(RATE_SCALE);
+            let fraction = (amount % duration) * RATE_SCALE / duration;
+            Some(whole.saturating_add(fraction))
+        }
+
+        /// Check a schedule's internal consistency: nothing claimed beyond its total and
+        /// a start before its end; returns `false` if the beneficiary has no schedule
+        /// Meant to catch corruption from bugs or storage migrations
+        #[ink(message)]
+        pub fn verify_invariants(&self, beneficiary: H160) -> bool {
+            self.get_vesting_schedule(beneficiary)
+                .is_some_and(|schedule| Self::invariants_hold(&schedule))
+        }
+
+        /// Get when more tokens next unlock (ms), or `None` if fully vested, suspended or no schedule
+        /// Continuous curves unlock all the time, so this is the current time once started
+        #[ink(message)]
+        pub fn next_unlock_time(&self, beneficiary: H160) -> Option<u64> {
+            let schedule = self.get_vesting_schedule(beneficiary)?;
+            let now = self.now();
+            // Nothing unlocks until the schedule is resumed
+            if now >= schedule.end_time || schedule.suspended_at.is_some() {
+                return None;
+            }
+            // A schedule revoked before its start has nothing left to unlock
+            if schedule.end_time <= schedule.start_time {
+                return None;
+            }
+            if now < schedule.start_time && schedule.initial_unlock > 0 {
+                return Some(schedule.start_time);
+            }
+
+            match schedule.kind {
+                VestingKind::Stepped { intervals } => {
+                    let elapsed = now.saturating_sub(schedule.start_time);
+                    let duration = schedule.end_time.saturating_sub(schedule.start_time);
+                    let completed = (elapsed as u128) * (intervals as u128) / (duration as u128);
+                    // First instant at which the next interval is complete
+                    let offset = (completed + 1)
+                        .saturating_mul(duration as u128)
+                        .div_ceil(intervals as u128);
+                    Some(schedule.start_time.saturating_add(offset as u64))
+                }
+                VestingKind::Periodic { period_ms } => {
+                    let elapsed = now.saturating_sub(schedule.start_time);
+                    let offset = (elapsed / period_ms + 1).saturating_mul(period_ms);
+                    Some(
+                        schedule
+                            .start_time
+                            .saturating_add(offset)
+                            .min(schedule.end_time),
+                    )
+                }
+                VestingKind::Monthly {
+                    months,
+                    day_of_month,
+                } => {
+                    let completed =
+                        self.completed_months(schedule.start_time, now, months, day_of_month);
+                    Some(self.month_boundary(schedule.start_time, completed + 1, day_of_month))
+                }
+                _ => Some(now.max(schedule.start_time)),
+            }
+        }
+
+        /// Project how much would be claimable at a hypothetical time
+        /// `at_time` - Timestamp in the configured unit
+        /// Fails with `NoVestingSchedule` if there is no (visible) schedule, and with
+        /// `Overflow` where a claim at that time would fail too
+        #[ink(message)]
+        pub fn simulate_claimable_at(&self, beneficiary: H160, at_time: u64) -> Result<Balance> {
+            self.claimable_at(beneficiary, self.to_millis(at_time))
+        }
+
+        /// Get the gross amount vested at a given (e.g. historical) time, ignoring claims
+        /// Evaluated against the schedule as stored now, so later extensions, top-ups or
+        /// revocations apply retroactively
+        /// Fails with `NoVestingSchedule` if there is no (visible) schedule, and with
+        /// `Overflow` if the amount can't be computed exactly
+        /// `at_time` - Timestamp in the configured unit
+        #[ink(message)]
+        pub fn vested_at(&self, beneficiary: H160, at_time: u64) -> Result<Balance> {
+            let schedule = self
+                .get_vesting_schedule(beneficiary)
+                .ok_or(Error::NoVestingSchedule)?;
+            self.calculate_vested_amount(&schedule, self.to_millis(at_time))
+        }
+
+        /// Get the earliest time (ms) at which `target_vested` tokens have vested
+        /// Works for every curve: linear schedules give the solution of the linear equation,
+        /// stepped and periodic ones the first boundary reaching the target
+        /// Returns `None` without a schedule or if the target is never reached
+        #[ink(message)]
+        pub fn time_for_amount(&self, beneficiary: H160, target_vested: Balance) -> Option<u64> {
+            let schedule = self.get_vesting_schedule(beneficiary)?;
+            // Also covers suspended schedules, which stop short of their total
+            if self
+                .calculate_vested_amount(&schedule, schedule.end_time)
+                .ok()?
+                < target_vested
+            {
+                return None;
+            }
+
+            // Vested amounts never decrease over time, so bisect [start, end]
+            let (mut low, mut high) = (schedule.start_time, schedule.end_time);
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if self.calculate_vested_amount(&schedule, mid).ok()? >= target_vested {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+            Some(low)
+        }
+
+        /// Get the amount each address could claim right now, in input order
+        /// Each entry fails like `simulate_claimable_at`; only the first 200 addresses are served
+        #[ink(message)]
+        pub fn get_claimable_batch(&self, beneficiaries: Vec<H160>) -> Vec<Result<Balance>> {
+            let now = self.now();
+            beneficiaries
+                .iter()
+                .take(MAX_BATCH_SIZE as usize)
+                .map(|beneficiary| self.claimable_at(*beneficiary, now))
+                .collect()
+        }
+
+        /// Check whether a beneficiary has a schedule without decoding it
+        #[ink(message)]
+        pub fn has_schedule(&self, beneficiary: H160) -> bool {
+            self.schedules.contains(beneficiary)
+        }
+
+        // Timestamp Conversion Functions (no_std compatible)
+        /// Convert Unix timestamp (milliseconds) to DateTime, or `None` past year 9999
+        /// Public views go through this so the 4-digit year field can never overflow
+        fn timestamp_to_datetime_checked(&self, timestamp_ms: u64) -> Option<DateTime> {
+            if timestamp_ms > MAX_SUPPORTED_TIMESTAMP_MS {
+                return None;
+            }
+            Some(self.timestamp_to_datetime(timestamp_ms))
+        }
+
+        /// Convert Unix timestamp (milliseconds) to DateTime
+        /// This demonstrates on-chain conversion but is typically done off-chain
+        /// u64 timestamps can't predate 1970
+        fn timestamp_to_datetime(&self, timestamp_ms: u64) -> DateTime {
+            // Split off the milliseconds
+            let millisecond = (timestamp_ms % 1000) as u16;
+            let timestamp = timestamp_ms / 1000;
 
             // Calculate seconds, minutes, hours
             let second = (timestamp % 60) as u8;
@@ -208,272 +2606,5584 @@ mod vesting_scheduler {
             let hour = (hours_total % 24) as u8;
             let days_total = hours_total / 24;
 
-            // Calculate year (accounting for leap years)
-            let mut year = 1970u32;
-            let mut remaining_days = days_total;
+            // Calculate year (accounting for leap years)
+            let (year, remaining_days) = Self::days_to_year(days_total);
+
+            // Calculate month and day
+            let (month, day) = Self::days_to_month_day(remaining_days as u32, year);
+
+            DateTime {
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                millisecond,
+            }
+        }
+        /// Split days since 1970-01-01 into (year, day of year) in constant time
+        /// Counts whole 400/100/4/1-year cycles from 1601-01-01, the start of a 400-year cycle
+        fn days_to_year(days_since_epoch: u64) -> (u32, u64) {
+            // 1601-01-01 to 1970-01-01
+            const DAYS_1601_TO_1970: u64 = 134_774;
+            const DAYS_PER_400Y: u64 = 146_097;
+            const DAYS_PER_100Y: u64 = 36_524;
+            const DAYS_PER_4Y: u64 = 1_461;
+
+            let mut days = days_since_epoch + DAYS_1601_TO_1970;
+            let cycles_400 = days / DAYS_PER_400Y;
+            days %= DAYS_PER_400Y;
+            // The last century (and year) of a cycle is one day longer
+            let centuries = (days / DAYS_PER_100Y).min(3);
+            days -= centuries * DAYS_PER_100Y;
+            let cycles_4 = days / DAYS_PER_4Y;
+            days %= DAYS_PER_4Y;
+            let years = (days / 365).min(3);
+            days -= years * 365;
+
+            let year = 1601 + cycles_400 * 400 + centuries * 100 + cycles_4 * 4 + years;
+            (year as u32, days)
+        }
+
+        /// Days from 1970-01-01 to January 1st of `year` (at least 1970) in constant time
+        fn days_before_year(year: u32) -> u64 {
+            // Leap years in 1..=y
+            let leaps = |y: u64| y / 4 - y / 100 + y / 400;
+            let year = year as u64;
+            (year - 1970) * 365 + leaps(year - 1) - leaps(1969)
+        }
+
+        /// Convert DateTime back to a Unix timestamp (milliseconds)
+        /// Inverse of `timestamp_to_datetime`; rejects fields outside the calendar
+        fn datetime_to_timestamp(&self, dt: DateTime) -> Result<u64> {
+            // Same range as `MAX_SUPPORTED_TIMESTAMP_MS`
+            if !(1970..=9999).contains(&dt.year)
+                || !(1..=12).contains(&dt.month)
+                || dt.day == 0
+                || dt.day > Self::days_in_month(dt.year, dt.month)
+                || dt.hour > 23
+                || dt.minute > 59
+                || dt.second > 59
+                || dt.millisecond > 999
+            {
+                return Err(Error::InvalidDateTime);
+            }
+
+            // Whole years since 1970
+            let mut days = Self::days_before_year(dt.year);
+
+            // Whole months of the current year
+            let days_in_months = if Self::is_leap_year(dt.year) {
+                [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+            } else {
+                [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+            };
+            days += days_in_months
+                .iter()
+                .take(dt.month.saturating_sub(1) as usize)
+                .sum::<u64>();
+            days += dt.day.saturating_sub(1) as u64;
+
+            let seconds =
+                days * 86_400 + dt.hour as u64 * 3_600 + dt.minute as u64 * 60 + dt.second as u64;
+            Ok(seconds * 1000 + dt.millisecond as u64)
+        }
+
+        /// Timestamp (ms) of the `k`-th monthly unlock after `start_ms`: `day_of_month`
+        /// (clamped to the month's length) of the k-th following month, at start's time of day
+        fn month_boundary(&self, start_ms: u64, k: u32, day_of_month: u8) -> u64 {
+            let start = self.timestamp_to_datetime(start_ms);
+            let month_index = (start.month as u32 - 1).saturating_add(k);
+            let year = start.year.saturating_add(month_index / 12);
+            let month = (month_index % 12 + 1) as u8;
+            // Every field comes from a valid date or is clamped; only a year past 9999
+            // fails, which saturates
+            self.datetime_to_timestamp(DateTime {
+                year,
+                month,
+                day: day_of_month.min(Self::days_in_month(year, month)),
+                hour: start.hour,
+                minute: start.minute,
+                second: start.second,
+                millisecond: start.millisecond,
+            })
+            .unwrap_or(u64::MAX)
+        }
+
+        /// Number of monthly unlocks (at most `months`) reached at `now_ms`
+        fn completed_months(
+            &self,
+            start_ms: u64,
+            now_ms: u64,
+            months: u32,
+            day_of_month: u8,
+        ) -> u32 {
+            let start = self.timestamp_to_datetime(start_ms);
+            let now = self.timestamp_to_datetime(now_ms);
+            let elapsed =
+                (now.year as i64 - start.year as i64) * 12 + now.month as i64 - start.month as i64;
+            let mut completed = elapsed.clamp(0, months as i64) as u32;
+            // This month's unlock may still be ahead
+            if completed > 0 && self.month_boundary(start_ms, completed, day_of_month) > now_ms {
+                completed -= 1;
+            }
+            completed
+        }
+
+        /// Number of days in a month (1-12)
+        fn days_in_month(year: u32, month: u8) -> u8 {
+            match month {
+                2 if Self::is_leap_year(year) => 29,
+                2 => 28,
+                4 | 6 | 9 | 11 => 30,
+                _ => 31,
+            }
+        }
+
+        /// Logarithmic bucket of a raw token amount: one bucket per factor of 1000
+        fn amount_bucket(amount: Balance) -> u8 {
+            amount
+                .checked_ilog10()
+                .map_or(0, |digits| (digits / 3) as u8)
+        }
+
+        /// Check if a year is a leap year
+        fn is_leap_year(year: u32) -> bool {
+            (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+        }
+
+        /// Convert day of year to month and day
+        /// day_of_year is 0-indexed (0 = Jan 1st)
+        fn days_to_month_day(day_of_year: u32, year: u32) -> (u8, u8) {
+            let is_leap = Self::is_leap_year(year);
+            let days_in_months = if is_leap {
+                [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+            } else {
+                [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+            };
+
+            let mut remaining = day_of_year;
+            for (i, &days) in days_in_months.iter().enumerate() {
+                if remaining < days {
+                    return ((i + 1) as u8, (remaining + 1) as u8);
+                }
+                remaining = remaining.saturating_sub(days);
+            }
+
+            // Fallback (shouldn't reach here with valid input)
+            (12, 31)
+        }
+
+        /// Format DateTime as a byte array: "YYYY-MM-DD HH:MM:SS"
+        /// Note: Returns fixed-size array for no_std compatibility
+        fn format_datetime(&self, dt: DateTime) -> [u8; 19] {
+            let mut result = [b'0'; 19];
+
+            // Format: YYYY-MM-DD HH:MM:SS
+            // Year (4 digits)
+            Self::write_u32(&mut result[0..4], dt.year);
+            result[4] = b'-';
+            // Month (2 digits)
+            Self::write_u8(&mut result[5..7], dt.month);
+            result[7] = b'-';
+            // Day (2 digits)
+            Self::write_u8(&mut result[8..10], dt.day);
+            result[10] = b' ';
+            // Hour (2 digits)
+            Self::write_u8(&mut result[11..13], dt.hour);
+            result[13] = b':';
+            // Minute (2 digits)
+            Self::write_u8(&mut result[14..16], dt.minute);
+            result[16] = b':';
+            // Second (2 digits)
+            Self::write_u8(&mut result[17..19], dt.second);
+
+            result
+        }
+
+        /// Format DateTime with milliseconds: "YYYY-MM-DD HH:MM:SS.mmm"
+        fn format_datetime_millis(&self, dt: DateTime) -> [u8; 23] {
+            let millisecond = dt.millisecond;
+            let mut result = [b'0'; 23];
+            result[..19].copy_from_slice(&self.format_datetime(dt));
+            result[19] = b'.';
+            // Millisecond (3 digits)
+            Self::write_u32(&mut result[20..23], millisecond as u32);
+            result
+        }
+
+        /// Write a u32 value to a byte buffer as ASCII digits
+        /// Values too wide for the buffer are clamped to all nines (e.g. year 12345 -> "9999")
+        fn write_u32(buf: &mut [u8], val: u32) {
+            let max = 10u64
+                .checked_pow(buf.len() as u32)
+                .map_or(u64::MAX, |limit| limit - 1);
+            let mut val = (val as u64).min(max);
+            for i in (0..buf.len()).rev() {
+                buf[i] = b'0' + (val % 10) as u8;
+                val /= 10;
+            }
+        }
+
+        /// Write a u8 value to a 2-byte buffer as ASCII digits
+        fn write_u8(buf: &mut [u8], val: u8) {
+            buf[0] = b'0' + (val / 10);
+            buf[1] = b'0' + (val % 10);
+        }
+
+        // Helper functions
+        // Ensures the caller is the contract owner
+        // A renounced (zero) owner matches no caller
+        fn ensure_owner(&self) -> Result<()> {
+            if self.owner.is_zero() || self.env().caller() != self.owner {
+                return Err(Error::Unauthorized);
+            }
+            Ok(())
+        }
+
+        // Ensures the caller holds the admin role
+        fn ensure_admin(&self) -> Result<()> {
+            if !self.admins.contains(self.env().caller()) {
+                return Err(Error::Unauthorized);
+            }
+            Ok(())
+        }
+
+        // Whether the caller may see a beneficiary's grant; every per-beneficiary view
+        // goes through this, so private schedules only show to the owner and the holder
+        fn can_view(&self, beneficiary: H160) -> bool {
+            let caller = self.env().caller();
+            !self.private_schedules || caller == beneficiary || caller == self.owner
+        }
+
+        // Emits the audit event for a privileged state change
+        fn record_admin_action(&self, kind: AdminActionKind, target: H160) {
+            if self.emits(EventVerbosity::Verbose) {
+                self.env().emit_event(AdminAction {
+                    action_kind: kind as u8,
+                    caller: self.env().caller(),
+                    target,
+                });
+            }
+        }
+
+        // Reports a moved end time; emitted before the operation's own event
+        fn record_time_change(
+            &self,
+            beneficiary: H160,
+            old_end_time: u64,
+            new_end_time: u64,
+            reason: TimeChangeReason,
+        ) {
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(ScheduleTimeChanged {
+                    beneficiary,
+                    old_end_time,
+                    new_end_time,
+                    reason: reason as u8,
+                });
+            }
+        }
+
+        // Invariants every stored schedule must satisfy; a revocation before the start
+        // collapses the range to a single instant
+        fn invariants_hold(schedule: &VestingSchedule) -> bool {
+            schedule.claimed_amount <= schedule.total_amount
+                && (schedule.start_time < schedule.end_time
+                    || (schedule.revoked && schedule.start_time == schedule.end_time))
+        }
+
+        // Whether events of the given level are emitted under the configured verbosity
+        fn emits(&self, level: EventVerbosity) -> bool {
+            self.event_verbosity >= level
+        }
+
+        // Moves a schedule and its per-beneficiary state from `from` to `to`
+        fn move_schedule(&mut self, from: H160, to: H160) -> Result<()> {
+            // Tokens granted to the zero address are locked forever
+            if to.is_zero() {
+                return Err(Error::InvalidBeneficiary);
+            }
+            let schedule = self.schedules.get(from).ok_or(Error::NoVestingSchedule)?;
+
+            // Never clobber an existing grant
+            if self.schedules.contains(to) {
+                return Err(Error::ScheduleAlreadyExists);
+            }
+
+            self.schedules.remove(from);
+            self.schedules.insert(to, &schedule);
+            // Keep the beneficiary's position in the list
+            if let Some(index) = self.beneficiary_index(from) {
+                self.beneficiaries.set(index, &to);
+            }
+            if let Some(history) = self.claim_history.take(from) {
+                self.claim_history.insert(to, &history);
+            }
+            if let Some(last_claim) = self.last_claim_time.take(from) {
+                self.last_claim_time.insert(to, &last_claim);
+            }
+            if let Some(count) = self.claim_count.take(from) {
+                self.claim_count.insert(to, &count);
+            }
+            // The whitelist can't be enumerated to copy it, so a restricted grant moves with
+            // an empty one: the new holder may only claim to itself until the owner re-adds
+            if self.restricted_recipients.contains(from) {
+                self.restricted_recipients.insert(to, &());
+            }
+            Ok(())
+        }
+
+        // Every address holding a schedule, in creation order, read one entry at a time
+        fn iter_beneficiaries(&self) -> impl Iterator<Item = H160> + '_ {
+            (0..self.beneficiaries.len()).filter_map(|index| self.beneficiaries.get(index))
+        }
+
+        // Position of `beneficiary` in the beneficiary list
+        fn beneficiary_index(&self, beneficiary: H160) -> Option<u32> {
+            (0..self.beneficiaries.len())
+                .find(|index| self.beneficiaries.get(*index) == Some(beneficiary))
+        }
+
+        // Drops `beneficiary` from the list, shifting later entries to keep creation order
+        fn remove_beneficiary(&mut self, beneficiary: H160) {
+            let Some(index) = self.beneficiary_index(beneficiary) else {
+                return;
+            };
+            for next in index.saturating_add(1)..self.beneficiaries.len() {
+                if let Some(moved) = self.beneficiaries.get(next) {
+                    self.beneficiaries.set(next - 1, &moved);
+                }
+            }
+            self.beneficiaries.pop();
+        }
+
+        // Extending or topping up a running schedule re-anchors it at the current time, which
+        // only preserves continuous curves; tranche boundaries would move to the new anchor
+        fn ensure_continuous(schedule: &VestingSchedule) -> Result<()> {
+            match schedule.kind {
+                VestingKind::Linear | VestingKind::Quadratic | VestingKind::SqrtFront => Ok(()),
+                _ => Err(Error::UnsupportedKind),
+            }
+        }
+
+        // Enforces the `max_duration` cap on a schedule running from `start` to `end` (ms)
+        fn check_max_duration(&self, start: u64, end: u64) -> Result<()> {
+            if self.max_duration_ms > 0 && end.saturating_sub(start) > self.max_duration_ms {
+                return Err(Error::DurationTooLong);
+            }
+            Ok(())
+        }
+
+        // Runs every creation-time check on a schedule (times already in ms)
+        fn validate_schedule(&self, beneficiary: H160, schedule: &VestingSchedule) -> Result<()> {
+            // Tokens granted to the zero address are locked forever
+            if beneficiary.is_zero() {
+                return Err(Error::InvalidBeneficiary);
+            }
+            // Overwriting would let the owner shrink an irrevocable grant
+            if self.schedules.contains(beneficiary) {
+                return Err(Error::ScheduleAlreadyExists);
+            }
+            if schedule.total_amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            if schedule.start_time >= schedule.end_time {
+                return Err(Error::InvalidTimeRange);
+            }
+            if schedule.initial_unlock > schedule.total_amount {
+                return Err(Error::InvalidInitialUnlock);
+            }
+            if matches!(
+                schedule.kind,
+                VestingKind::Stepped { intervals: 0 }
+                    | VestingKind::Periodic { period_ms: 0 }
+                    | VestingKind::Monthly { months: 0, .. }
+            ) {
+                return Err(Error::InvalidIntervals);
+            }
+            self.check_max_duration(schedule.start_time, schedule.end_time)?;
+            let duration = schedule.end_time.saturating_sub(schedule.start_time);
+            if duration < self.min_duration_ms {
+                return Err(Error::DurationTooShort);
+            }
+            // Guard against fat-fingered start times that would vest instantly
+            let now = self.now();
+            if self.max_backdate_ms > 0
+                && schedule.start_time < now.saturating_sub(self.max_backdate_ms)
+            {
+                return Err(Error::StartTimeTooFarInPast);
+            }
+            // A grant that has already ended vests instantly, which is rarely intended
+            if !self.allow_past_end && schedule.end_time <= now {
+                return Err(Error::EndTimeInPast);
+            }
+            if self.max_beneficiaries > 0 && self.beneficiaries.len() >= self.max_beneficiaries {
+                return Err(Error::TooManyBeneficiaries);
+            }
+            Ok(())
+        }
+
+        // Validates and stores a new schedule (times already in ms)
+        fn add_schedule(&mut self, beneficiary: H160, schedule: VestingSchedule) -> Result<()> {
+            self.validate_schedule(beneficiary, &schedule)?;
+            self.beneficiaries.push(&beneficiary);
+            self.total_vested_allocated = self
+                .total_vested_allocated
+                .saturating_add(schedule.total_amount);
+            self.schedules.insert(beneficiary, &schedule);
+            self.record_admin_action(AdminActionKind::CreateSchedule, beneficiary);
+            if self.emits(EventVerbosity::Standard) {
+                self.env().emit_event(VestingCreated {
+                    beneficiary,
+                    total_amount: schedule.total_amount,
+                    start_time: schedule.start_time,
+                    end_time: schedule.end_time,
+                });
+            }
+            if self.emits(EventVerbosity::Verbose) {
+                let start_dt = self.timestamp_to_datetime(schedule.start_time);
+                let end_dt = self.timestamp_to_datetime(schedule.end_time);
+                self.env().emit_event(VestingCreatedReadable {
+                    beneficiary,
+                    total_amount: schedule.total_amount,
+                    start_readable: self.format_datetime(start_dt),
+                    end_readable: self.format_datetime(end_dt),
+                });
+            }
+            Ok(())
+        }
+
+        // Hash a beneficiary signs to authorize a meta claim
+        fn meta_claim_hash(&self, beneficiary: H160, nonce: u64) -> [u8; 32] {
+            self.env()
+                .hash_encoded::<Keccak256, _>(&(beneficiary, nonce, self.env().address()))
+        }
+
+        // Recovers the Ethereum-style address that produced `signature` over `message_hash`
+        fn recover_signer(&self, signature: &[u8; 65], message_hash: &[u8; 32]) -> Result<H160> {
+            let mut public_key = [0u8; 33];
+            self.env()
+                .ecdsa_recover(signature, message_hash, &mut public_key)
+                .map_err(|_| Error::InvalidSignature)?;
+            let mut address = [0u8; 20];
+            self.env()
+                .ecdsa_to_eth_address(&public_key, &mut address)
+                .map_err(|_| Error::InvalidSignature)?;
+            Ok(H160::from(address))
+        }
+
+        // Native balance held by the contract
+        fn contract_balance(&self) -> Balance {
+            Balance::try_from(self.env().balance()).unwrap_or(Balance::MAX)
+        }
+
+        // Everything the contract must keep funded: unclaimed grants, claimed tokens it still
+        // holds, unwithdrawn fees and reclaimable revocations, or the `fund()` deposits not
+        // yet paid out if those are larger
+        fn obligations(&self) -> Balance {
+            let owed = self
+                .total_outstanding()
+                .saturating_add(self.claimed_unpaid)
+                .saturating_add(self.unpaid_fees)
+                .saturating_add(self.reclaimable);
+            let deposits = self.total_funded.saturating_sub(self.total_withdrawn);
+            owed.max(deposits)
+        }
+
+        // Normalizes a timestamp in the configured unit to milliseconds
+        fn to_millis(&self, timestamp: u64) -> u64 {
+            match self.timestamp_unit {
+                TimestampUnit::Millis => timestamp,
+                TimestampUnit::Seconds => timestamp.saturating_mul(1000),
+            }
+        }
+
+        // Converts a caller-supplied start time to ms, treating 0 as "now"
+        fn resolve_start_time(&self, start_time: u64) -> u64 {
+            if start_time == 0 {
+                self.now()
+            } else {
+                self.to_millis(start_time)
+            }
+        }
+
+        // Current block time in milliseconds
+        fn now(&self) -> u64 {
+            self.to_millis(self.env().block_timestamp())
+        }
+
+        // Appends a claim to the beneficiary's history, dropping the oldest entries beyond the cap
+        fn record_claim(&mut self, beneficiary: H160, timestamp: u64, amount: Balance) {
+            if self.max_claim_history == 0 {
+                return;
+            }
+            let mut history = self.claim_history.get(beneficiary).unwrap_or_default();
+            history.push((timestamp, amount));
+            let max = self.max_claim_history as usize;
+            if history.len() > max {
+                history.drain(..history.len() - max);
+            }
+            self.claim_history.insert(beneficiary, &history);
+        }
+
+        // Vested but unclaimed amount at `time_ms`; 0 without a schedule
+        fn claimable_at(&self, beneficiary: H160, time_ms: u64) -> Result<Balance> {
+            let schedule = self
+                .get_vesting_schedule(beneficiary)
+                .ok_or(Error::NoVestingSchedule)?;
+            Ok(self
+                .calculate_vested_amount(&schedule, time_ms)?
+                .saturating_sub(schedule.claimed_amount))
+        }
+
+        // The time a schedule has accrued up to: accrual stops while it is suspended
+        fn accrual_time(schedule: &VestingSchedule, now: u64) -> u64 {
+            schedule.suspended_at.map_or(now, |at| now.min(at))
+        }
+
+        // Calculates the amount vested along the schedule's curve
+        // At exactly start_time no time has elapsed, so only the initial unlock has vested
+        // (a claim then fails with `NoTokensAvailable` unless there is one)
+        // Fails with `Overflow` unless the `saturating-math` feature is enabled
+        fn calculate_vested_amount(
+            &self,
+            schedule: &VestingSchedule,
+            current_time: u64,
+        ) -> Result<Balance> {
+            let current_time = Self::accrual_time(schedule, current_time);
+            if current_time < schedule.start_time {
+                return Ok(0);
+            }
+
+            if current_time >= schedule.end_time {
+                return Ok(schedule.total_amount);
+            }
+
+            let elapsed = current_time.saturating_sub(schedule.start_time);
+            let duration = schedule.end_time.saturating_sub(schedule.start_time);
+            let rounding = schedule.rounding.unwrap_or(self.rounding_mode);
+            // Only the part beyond the initial unlock follows the curve
+            let vesting_amount = schedule
+                .total_amount
+                .saturating_sub(schedule.initial_unlock);
+
+            let vested = match schedule.kind {
+                // vested = (total * elapsed) / duration
+                VestingKind::Linear => Self::mul_div(vesting_amount, elapsed, duration, rounding)?,
+                // vested = (total * elapsed / duration) * elapsed / duration
+                VestingKind::Quadratic => {
+                    let linear =
+                        Self::mul_div(vesting_amount, elapsed, duration, RoundingMode::Floor)?;
+                    Self::mul_div(linear, elapsed, duration, rounding)?
+                }
+                // vested = total * sqrt(elapsed * duration) / duration
+                VestingKind::SqrtFront => {
+                    let root = ((elapsed as u128) * (duration as u128)).isqrt() as u64;
+                    Self::mul_div(vesting_amount, root, duration, rounding)?
+                }
+                // vested = total * completed_intervals / intervals
+                VestingKind::Stepped { intervals } => {
+                    let completed =
+                        ((elapsed as u128) * (intervals as u128) / (duration as u128)) as u64;
+                    Self::mul_div(vesting_amount, completed, intervals as u64, rounding)?
+                }
+                // vested = total * (whole periods elapsed * period) / duration
+                VestingKind::Periodic { period_ms } => {
+                    let unlocked = elapsed / period_ms * period_ms;
+                    Self::mul_div(vesting_amount, unlocked, duration, rounding)?
+                }
+                // vested = total * months passed / months
+                VestingKind::Monthly {
+                    months,
+                    day_of_month,
+                } => {
+                    let completed = self.completed_months(
+                        schedule.start_time,
+                        current_time,
+                        months,
+                        day_of_month,
+                    );
+                    Self::mul_div(vesting_amount, completed as u64, months as u64, rounding)?
+                }
+            };
+
+            // The guaranteed floor accrues per whole day since start
+            let floor = schedule
+                .min_vest_per_day
+                .saturating_mul(Balance::from(elapsed / MS_PER_DAY));
+
+            // Rounding up must never release more than the grant
+            Ok(schedule
+                .initial_unlock
+                .saturating_add(vested)
+                .max(floor)
+                .min(schedule.total_amount))
+        }
+
+        // Computes value * numerator / denominator, failing on overflow
+        // (or clamping at Balance::MAX with the `saturating-math` feature)
+        fn mul_div(
+            value: Balance,
+            numerator: u64,
+            denominator: u64,
+            rounding: RoundingMode,
+        ) -> Result<Balance> {
+            #[cfg(feature = "saturating-math")]
+            let product = value.saturating_mul(numerator as u128);
+            #[cfg(not(feature = "saturating-math"))]
+            let product = value
+                .checked_mul(numerator as u128)
+                .ok_or(Error::Overflow)?;
+            let denominator = denominator as u128;
+            let quotient = product.checked_div(denominator).ok_or(Error::Overflow)?;
+            let remainder = product % denominator;
+
+            let round_up = match rounding {
+                RoundingMode::Floor => false,
+                RoundingMode::Ceil => remainder > 0,
+                RoundingMode::Nearest => remainder.saturating_mul(2) >= denominator,
+            };
+            if round_up {
+                Ok(quotient.saturating_add(1))
+            } else {
+                Ok(quotient)
+            }
+        }
+    }
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// Creates an unlabelled schedule
+        fn create_schedule(
+            contract: &mut VestingScheduler,
+            beneficiary: H160,
+            total_amount: Balance,
+            start_time: u64,
+            end_time: u64,
+        ) -> Result<()> {
+            contract.create_vesting_schedule(
+                beneficiary,
+                total_amount,
+                start_time,
+                end_time,
+                ScheduleParams::default(),
+            )
+        }
+
+        /// Decodes the most recently emitted event as `E`
+        fn last_event<E: ink::scale::Decode>() -> E {
+            nth_last_event(0)
+        }
+
+        /// Decodes the event emitted `n` events before the most recent one as `E`
+        fn nth_last_event<E: ink::scale::Decode>(n: usize) -> E {
+            let events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let event = events
+                .iter()
+                .rev()
+                .nth(n)
+                .expect("not enough events were emitted");
+            E::decode(&mut &event.data[..]).expect("encountered invalid event data")
+        }
+
+        /// Address of the contract under test
+        fn contract_address() -> H160 {
+            ink::env::test::callee::<ink::env::DefaultEnvironment>()
+        }
+
+        /// Zero-pads a label to 32 bytes
+        fn label(text: &[u8]) -> [u8; 32] {
+            let mut label = [0u8; 32];
+            label[..text.len()].copy_from_slice(text);
+            label
+        }
+
+        #[ink::test]
+        fn test_vesting_lifecycle() {
+            let accounts = ink::env::test::default_accounts();
+            // Convert AccountId to H160
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([1u8; 20]);
+
+            // Set caller to owner BEFORE creating contract
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // Set initial block timestamp: Oct 21, 2024, 10:00:00 UTC
+            let start_time = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start_time);
+
+            // Create vesting schedule: 1M tokens over 100 days
+            let total_amount = 1_000_000;
+            let end_time = start_time + (100 * 24 * 60 * 60 * 1000); // 100 days later
+
+            let result = create_schedule(
+                &mut contract,
+                beneficiary,
+                total_amount,
+                start_time,
+                end_time,
+            );
+            assert!(
+                result.is_ok(),
+                "create_vesting_schedule failed: {:?}",
+                result
+            );
+
+            // Switch caller to beneficiary to claim
+            ink::env::test::set_caller(beneficiary);
+
+            // Advance time by 50 days
+            let fifty_days_later = start_time + (50 * 24 * 60 * 60 * 1000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(fifty_days_later);
+
+            // Should be able to claim 50% of tokens
+            let claimed = contract.claim_vested().unwrap();
+            assert_eq!(claimed, 500_000);
+
+            // Advance to after vesting ends
+            let after_end = end_time + 1000;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(after_end);
+
+            // Should be able to claim remaining 50%
+            let remaining = contract.claim_vested().unwrap();
+            assert_eq!(remaining, 500_000);
+
+            // No more tokens to claim
+            let result = contract.claim_vested();
+            assert_eq!(result, Err(Error::NoTokensAvailable));
+        }
+
+        #[ink::test]
+        fn test_timestamp_conversion() {
+            let contract = VestingScheduler::new();
+
+            // Test known timestamp: Oct 21, 2024, 12:00:00 UTC
+            let timestamp = 1729512000000u64;
+            let dt = contract.timestamp_to_datetime(timestamp);
+
+            assert_eq!(dt.year, 2024);
+            assert_eq!(dt.month, 10);
+            assert_eq!(dt.day, 21);
+            assert_eq!(dt.hour, 12);
+            assert_eq!(dt.minute, 0);
+            assert_eq!(dt.second, 0);
+
+            // Test formatting
+            let formatted = contract.format_datetime(dt);
+            let expected = b"2024-10-21 12:00:00";
+            assert_eq!(&formatted[..], expected);
+        }
+
+        #[ink::test]
+        fn test_leap_year() {
+            let contract = VestingScheduler::new();
+
+            // Test leap year: Mar 1, 2024
+            let leap_day = 1709251200000u64; // 2024-03-01 00:00:00 UTC
+            let dt = contract.timestamp_to_datetime(leap_day);
+
+            assert_eq!(dt.year, 2024);
+            assert_eq!(dt.month, 3);
+            assert_eq!(dt.day, 1);
+        }
+
+        #[ink::test]
+        fn test_vesting_not_started() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into(); // Convert AccountId to H160
+            let beneficiary: H160 = H160::from([2u8; 20]);
+
+            let current = 1729512000000u64;
+            let future_start = current + (10 * 24 * 60 * 60 * 1000); // 10 days from now
+            let future_end = future_start + (100 * 24 * 60 * 60 * 1000);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(current);
+
+            // Set caller to owner BEFORE creating contract
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let result = create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                future_start,
+                future_end,
+            );
+            assert!(
+                result.is_ok(),
+                "create_vesting_schedule failed: {:?}",
+                result
+            );
+
+            // Switch to beneficiary to claim
+            ink::env::test::set_caller(beneficiary);
+
+            // Try to claim before vesting starts
+            let result = contract.claim_vested();
+            assert_eq!(result, Err(Error::VestingNotStarted));
+        }
+
+        #[ink::test]
+        fn test_readable_schedule_view() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into(); // Convert AccountId to H160
+            let beneficiary: H160 = H160::from([3u8; 20]);
+
+            // Set caller to owner BEFORE creating contract
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64; // 2024-10-21 12:00:00
+            let end = 1737374400000u64; // 2025-01-20 12:00:00
+
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert!(
+                result.is_ok(),
+                "create_vesting_schedule failed: {:?}",
+                result
+            );
+
+            let result = contract.get_vesting_schedule_readable(beneficiary);
+            assert!(result.is_some());
+
+            let (schedule, start_readable, end_readable) = result.unwrap();
+            assert_eq!(schedule.total_amount, 1_000_000);
+            assert_eq!(&start_readable[..], b"2024-10-21 12:00:00");
+            assert_eq!(&end_readable[..], b"2025-01-20 12:00:00");
+        }
+
+        #[ink::test]
+        fn test_backdated_start_within_window() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([4u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let now = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+
+            // Allow up to 7 days of backdating
+            let seven_days = 7 * 24 * 60 * 60 * 1000;
+            assert_eq!(contract.set_max_backdate(seven_days), Ok(()));
+
+            // Start 1 day ago is acceptable
+            let start = now - (24 * 60 * 60 * 1000);
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+        }
+
+        #[ink::test]
+        fn test_backdated_start_too_far_in_past() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([5u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let now = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+
+            let seven_days = 7 * 24 * 60 * 60 * 1000;
+            assert_eq!(contract.set_max_backdate(seven_days), Ok(()));
+
+            // Start 30 days ago exceeds the window
+            let start = now - (30 * 24 * 60 * 60 * 1000);
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Err(Error::StartTimeTooFarInPast));
+
+            // Only the owner may configure the window
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.set_max_backdate(0), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn test_vesting_curves() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let midpoint = start + (50 * 24 * 60 * 60 * 1000);
+
+            // (kind, expected vested amount at the midpoint)
+            let cases = [
+                (VestingKind::Linear, 500_000),
+                (VestingKind::Quadratic, 250_000),
+                (VestingKind::SqrtFront, 707_106),
+            ];
+
+            for (i, (kind, expected_mid)) in cases.into_iter().enumerate() {
+                let beneficiary = H160::from([10 + i as u8; 20]);
+                let result = contract.create_vesting_schedule_with_kind(
+                    beneficiary,
+                    1_000_000,
+                    start,
+                    end,
+                    kind,
+                );
+                assert_eq!(result, Ok(()));
+
+                let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+                assert_eq!(schedule.kind, kind);
+                assert_eq!(contract.calculate_vested_amount(&schedule, start), Ok(0));
+                assert_eq!(
+                    contract.calculate_vested_amount(&schedule, midpoint),
+                    Ok(expected_mid)
+                );
+                assert_eq!(
+                    contract.calculate_vested_amount(&schedule, end),
+                    Ok(1_000_000)
+                );
+            }
+        }
+
+        #[ink::test]
+        fn test_claim_reentrancy_guard() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([6u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+
+            // Simulate a nested call arriving while a claim is in progress
+            contract.locked = true;
+            assert_eq!(contract.claim_vested(), Err(Error::Reentrancy));
+            contract.locked = false;
+
+            // An error path must release the lock
+            ink::env::test::set_caller(owner);
+            assert_eq!(contract.claim_vested(), Err(Error::NoVestingSchedule));
+            assert!(!contract.locked);
+
+            // A successful claim releases the lock as well
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.claim_vested(), Ok(1_000_000));
+            assert!(!contract.locked);
+        }
+
+        #[ink::test]
+        fn test_has_schedule() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([7u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            assert!(!contract.has_schedule(beneficiary));
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            assert!(contract.has_schedule(beneficiary));
+            assert!(!contract.has_schedule(H160::from([8u8; 20])));
+        }
+
+        #[ink::test]
+        fn test_transfer_schedule() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let old_wallet: H160 = H160::from([9u8; 20]);
+            let new_wallet: H160 = H160::from([20u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let result = create_schedule(&mut contract, old_wallet, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            // Claim half before rotating wallets
+            ink::env::test::set_caller(old_wallet);
+            let midpoint = start + (50 * 24 * 60 * 60 * 1000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(midpoint);
+            assert_eq!(contract.claim_vested(), Ok(500_000));
+
+            assert_eq!(contract.transfer_schedule(new_wallet), Ok(()));
+            assert!(!contract.has_schedule(old_wallet));
+
+            // Claimed progress moves with the schedule
+            let schedule = contract.get_vesting_schedule(new_wallet).unwrap();
+            assert_eq!(schedule.claimed_amount, 500_000);
+
+            ink::env::test::set_caller(new_wallet);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            assert_eq!(contract.claim_vested(), Ok(500_000));
+        }
+
+        #[ink::test]
+        fn test_transfer_schedule_rejects_collision() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let first: H160 = H160::from([21u8; 20]);
+            let second: H160 = H160::from([22u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            assert_eq!(
+                create_schedule(&mut contract, first, 1_000_000, start, end),
+                Ok(())
+            );
+            assert_eq!(
+                create_schedule(&mut contract, second, 2_000_000, start, end),
+                Ok(())
+            );
+
+            ink::env::test::set_caller(first);
+            assert_eq!(
+                contract.transfer_schedule(second),
+                Err(Error::ScheduleAlreadyExists)
+            );
+
+            // Both grants are untouched
+            assert_eq!(
+                contract.get_vesting_schedule(first).unwrap().total_amount,
+                1_000_000
+            );
+            assert_eq!(
+                contract.get_vesting_schedule(second).unwrap().total_amount,
+                2_000_000
+            );
+        }
+
+        #[ink::test]
+        fn test_timestamp_conversion_year_9999_boundary() {
+            let contract = VestingScheduler::new();
+
+            // Last representable millisecond
+            let dt = contract
+                .timestamp_to_datetime_checked(MAX_SUPPORTED_TIMESTAMP_MS)
+                .unwrap();
+            let formatted = contract.format_datetime(dt);
+            assert_eq!(&formatted[..], b"9999-12-31 23:59:59");
+
+            // One millisecond later would need a 5-digit year
+            assert!(
+                contract
+                    .timestamp_to_datetime_checked(MAX_SUPPORTED_TIMESTAMP_MS + 1)
+                    .is_none()
+            );
+        }
+
+        #[ink::test]
+        fn test_readable_view_rejects_out_of_range_dates() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([23u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = MAX_SUPPORTED_TIMESTAMP_MS + 1;
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            assert!(
+                contract
+                    .get_vesting_schedule_readable(beneficiary)
+                    .is_none()
+            );
+            assert!(contract.get_vesting_schedule(beneficiary).is_some());
+        }
+
+        #[ink::test]
+        fn test_rounding_modes() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([24u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // 10 tokens over 3 seconds: 3.33 tokens vest per second
+            let start = 1729512000000u64;
+            let end = start + 3000;
+            let result = create_schedule(&mut contract, beneficiary, 10, start, end);
+            assert_eq!(result, Ok(()));
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+
+            // Floor is the default
+            assert_eq!(
+                contract.calculate_vested_amount(&schedule, start + 1000),
+                Ok(3)
+            );
+
+            assert_eq!(contract.set_rounding_mode(RoundingMode::Nearest), Ok(()));
+            assert_eq!(
+                contract.calculate_vested_amount(&schedule, start + 1000),
+                Ok(3)
+            );
+            assert_eq!(
+                contract.calculate_vested_amount(&schedule, start + 2000),
+                Ok(7)
+            );
+
+            assert_eq!(contract.set_rounding_mode(RoundingMode::Ceil), Ok(()));
+            assert_eq!(
+                contract.calculate_vested_amount(&schedule, start + 1000),
+                Ok(4)
+            );
+        }
+
+        #[ink::test]
+        fn test_ceil_rounding_caps_at_total() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([25u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.set_rounding_mode(RoundingMode::Ceil), Ok(()));
+
+            let start = 1729512000000u64;
+            let end = start + 3000;
+            let result = create_schedule(&mut contract, beneficiary, 10, start, end);
+            assert_eq!(result, Ok(()));
+
+            // Ceil releases the extra unit early...
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start + 1000);
+            assert_eq!(contract.claim_vested(), Ok(4));
+
+            // ...including the final one a millisecond before the end
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end - 1);
+            assert_eq!(contract.claim_vested(), Ok(6));
+
+            // ...but never more than the total
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            assert_eq!(contract.claim_vested(), Err(Error::NoTokensAvailable));
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.claimed_amount, 10);
+        }
+
+        #[ink::test]
+        fn test_claim_history_grows_and_trims() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([26u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.set_max_claim_history(3), Ok(()));
+
+            // 1M tokens over 10 days: 100k per day
+            let day = 24 * 60 * 60 * 1000;
+            let start = 1729512000000u64;
+            let end = start + 10 * day;
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            ink::env::test::set_caller(beneficiary);
+            assert!(contract.get_claim_history(beneficiary).is_empty());
+
+            // History grows with each claim
+            for i in 1..=3u64 {
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                    start + i * day,
+                );
+                assert_eq!(contract.claim_vested(), Ok(100_000));
+                assert_eq!(contract.get_claim_history(beneficiary).len(), i as usize);
+            }
+
+            // Beyond the cap the oldest entries are dropped
+            for i in 4..=5u64 {
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                    start + i * day,
+                );
+                assert_eq!(contract.claim_vested(), Ok(100_000));
+            }
+            let history = contract.get_claim_history(beneficiary);
+            assert_eq!(
+                history,
+                vec![
+                    (start + 3 * day, 100_000),
+                    (start + 4 * day, 100_000),
+                    (start + 5 * day, 100_000),
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn test_operator_claims_for_beneficiary() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([27u8; 20]);
+            let keeper: H160 = H160::from([28u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            ink::env::test::set_caller(beneficiary);
+            contract.set_operator(keeper);
+            assert_eq!(contract.get_operator(beneficiary), Some(keeper));
+
+            // The keeper triggers the claim; progress is recorded for the beneficiary
+            ink::env::test::set_caller(keeper);
+            let midpoint = start + (50 * 24 * 60 * 60 * 1000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(midpoint);
+            assert_eq!(contract.claim_vested_for(beneficiary), Ok(500_000));
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.claimed_amount, 500_000);
+
+            // The beneficiary can always claim for themselves
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            assert_eq!(contract.claim_vested_for(beneficiary), Ok(500_000));
+        }
+
+        #[ink::test]
+        fn test_unauthorized_operator_rejected() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([29u8; 20]);
+            let keeper: H160 = H160::from([30u8; 20]);
+            let stranger: H160 = H160::from([31u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+
+            // No operator approved yet
+            ink::env::test::set_caller(keeper);
+            assert_eq!(
+                contract.claim_vested_for(beneficiary),
+                Err(Error::UnauthorizedOperator)
+            );
+
+            ink::env::test::set_caller(beneficiary);
+            contract.set_operator(keeper);
+
+            // Only the approved operator may claim
+            ink::env::test::set_caller(stranger);
+            assert_eq!(
+                contract.claim_vested_for(beneficiary),
+                Err(Error::UnauthorizedOperator)
+            );
+        }
+
+        #[ink::test]
+        fn test_timestamp_units() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([32u8; 20]);
+
+            // The same schedule (2024-10-21 12:00:00 for 100 days) in both units
+            let start_secs = 1729512000u64;
+            let end_secs = start_secs + (100 * 24 * 60 * 60);
+            let midpoint_secs = start_secs + (50 * 24 * 60 * 60);
+
+            for unit in [TimestampUnit::Millis, TimestampUnit::Seconds] {
+                let scale = match unit {
+                    TimestampUnit::Millis => 1000,
+                    TimestampUnit::Seconds => 1,
+                };
+
+                // Each unit starts with the clock at the schedule start
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                    start_secs * scale,
+                );
+                ink::env::test::set_caller(owner);
+                let mut contract = VestingScheduler::new_with_config(unit, DEFAULT_DECIMALS, false);
+                let result = create_schedule(
+                    &mut contract,
+                    beneficiary,
+                    1_000_000,
+                    start_secs * scale,
+                    end_secs * scale,
+                );
+                assert_eq!(result, Ok(()));
+
+                // Stored times are normalized to milliseconds
+                let (schedule, start_readable, end_readable) =
+                    contract.get_vesting_schedule_readable(beneficiary).unwrap();
+                assert_eq!(schedule.start_time, start_secs * 1000);
+                assert_eq!(&start_readable[..], b"2024-10-21 12:00:00");
+                assert_eq!(&end_readable[..], b"2025-01-29 12:00:00");
+
+                ink::env::test::set_caller(beneficiary);
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                    midpoint_secs * scale,
+                );
+                assert_eq!(contract.claim_vested(), Ok(500_000));
+            }
+        }
+
+        #[ink::test]
+        fn test_schedule_label_round_trip() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([33u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let grant_label = label(b"Q3-2024 advisor grant");
+            let params = ScheduleParams {
+                label: grant_label,
+                ..Default::default()
+            };
+            let result =
+                contract.create_vesting_schedule(beneficiary, 1_000_000, start, end, params);
+            assert_eq!(result, Ok(()));
+
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.label, grant_label);
+            assert_eq!(&schedule.label[..21], b"Q3-2024 advisor grant");
+            assert!(schedule.label[21..].iter().all(|&b| b == 0));
+
+            let (schedule, _, _) = contract.get_vesting_schedule_readable(beneficiary).unwrap();
+            assert_eq!(schedule.label, grant_label);
+        }
+
+        #[ink::test]
+        fn test_initial_unlock_then_linear() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([34u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // 20% released at TGE, the remaining 800k over 100 days
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let params = ScheduleParams {
+                initial_unlock: 200_000,
+                ..Default::default()
+            };
+            let result =
+                contract.create_vesting_schedule(beneficiary, 1_000_000, start, end, params);
+            assert_eq!(result, Ok(()));
+
+            // The TGE chunk is claimable the instant vesting starts
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            assert_eq!(contract.claim_vested(), Ok(200_000));
+
+            // Half-way through, half of the remainder has vested
+            let midpoint = start + (50 * 24 * 60 * 60 * 1000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(midpoint);
+            assert_eq!(contract.claim_vested(), Ok(400_000));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            assert_eq!(contract.claim_vested(), Ok(400_000));
+        }
+
+        #[ink::test]
+        fn test_initial_unlock_exceeding_total_rejected() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([35u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let params = ScheduleParams {
+                initial_unlock: 1_000_001,
+                ..Default::default()
+            };
+            let result =
+                contract.create_vesting_schedule(beneficiary, 1_000_000, start, end, params);
+            assert_eq!(result, Err(Error::InvalidInitialUnlock));
+        }
+
+        #[ink::test]
+        fn test_claim_fee_split() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([36u8; 20]);
+            let treasury: H160 = H160::from([37u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // 2.5% fee
+            assert_eq!(contract.set_claim_fee_bps(250), Ok(()));
+            assert_eq!(contract.set_fee_recipient(treasury), Ok(()));
+            assert_eq!(contract.get_claim_fee_bps(), 250);
+            assert_eq!(contract.get_fee_recipient(), treasury);
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            ink::env::test::set_caller(beneficiary);
+            let midpoint = start + (50 * 24 * 60 * 60 * 1000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(midpoint);
+
+            // 500k vested: 12.5k to the treasury, 487.5k to the beneficiary
+            assert_eq!(contract.claim_vested(), Ok(487_500));
+            let fee: FeeCollected = last_event();
+            assert_eq!(fee.beneficiary, beneficiary);
+            assert_eq!(fee.recipient, treasury);
+            assert_eq!(fee.amount, 12_500);
+
+            // The full gross amount counts as claimed
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.claimed_amount, 500_000);
+            assert_eq!(contract.get_fee_balance(treasury), 12_500);
+        }
+
+        #[ink::test]
+        fn test_withdraw_fees() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([186u8; 20]);
+            let treasury = H160::from([187u8; 20]);
+            let start = 1729512000000u64;
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.set_fee_config(treasury, 1_000), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000,
+                start,
+                start + 10 * MS_PER_DAY,
+            )
+            .unwrap();
+            ink::env::test::set_contract_balance(contract_address(), U256::from(1_000u128));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 5 * MS_PER_DAY,
+            );
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.claim_vested(), Ok(450));
+
+            // The fee is owed to the treasury, so the owner can't sweep it
+            ink::env::test::set_caller(owner);
+            assert_eq!(contract.sweep_untracked(), Err(Error::NoTokensAvailable));
+            assert_eq!(contract.withdraw_fees(), Err(Error::NoTokensAvailable));
+
+            ink::env::test::set_caller(treasury);
+            assert_eq!(contract.withdraw_fees(), Ok(50));
+            let event: FeesWithdrawn = last_event();
+            assert_eq!(event.recipient, treasury);
+            assert_eq!(event.amount, 50);
+            assert_eq!(contract.get_fee_balance(treasury), 0);
+            assert_eq!(contract.withdraw_fees(), Err(Error::NoTokensAvailable));
+            assert_eq!(contract.reconcile(), (950, 950, true));
+        }
+
+        #[ink::test]
+        fn test_claim_fee_above_100_percent_rejected() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            assert_eq!(contract.set_claim_fee_bps(10_001), Err(Error::InvalidFee));
+            assert_eq!(contract.set_claim_fee_bps(10_000), Ok(()));
+        }
+
+        #[ink::test]
+        fn test_version_and_schema() {
+            let contract = VestingScheduler::new();
+            assert_eq!(contract.get_version(), CONTRACT_VERSION);
+            assert_eq!(contract.get_version(), 3);
+            assert_eq!(&contract.storage_schema(), b"VSC3");
+
+            let contract =
+                VestingScheduler::new_with_config(TimestampUnit::Seconds, DEFAULT_DECIMALS, false);
+            assert_eq!(contract.get_version(), CONTRACT_VERSION);
+        }
+
+        #[ink::test]
+        fn test_underfunded_beneficiaries() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let first: H160 = H160::from([38u8; 20]);
+            let second: H160 = H160::from([39u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            assert_eq!(
+                create_schedule(&mut contract, first, 1_000_000, start, end),
+                Ok(())
+            );
+            assert_eq!(
+                create_schedule(&mut contract, second, 1_000_000, start, end),
+                Ok(())
+            );
+            assert_eq!(contract.get_all_beneficiaries(), vec![first, second]);
+
+            // Fully funded
+            ink::env::test::set_contract_balance(contract_address(), U256::from(2_000_000u128));
+            assert!(contract.get_underfunded_beneficiaries().is_empty());
+
+            // Only enough for the first grant and half of the second
+            ink::env::test::set_contract_balance(contract_address(), U256::from(1_500_000u128));
+            assert_eq!(contract.get_underfunded_beneficiaries(), vec![second]);
+
+            // Claimed tokens stay in the contract, so a claim frees no balance
+            ink::env::test::set_caller(second);
+            let midpoint = start + (50 * 24 * 60 * 60 * 1000);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(midpoint);
+            assert_eq!(contract.claim_vested(), Ok(500_000));
+            assert_eq!(contract.get_underfunded_beneficiaries(), vec![second]);
+            assert!(!contract.reconcile().2);
+
+            // Revoked tokens awaiting withdrawal are set aside as well
+            ink::env::test::set_caller(owner);
+            ink::env::test::set_contract_balance(contract_address(), U256::from(2_000_000u128));
+            assert!(contract.get_underfunded_beneficiaries().is_empty());
+            assert_eq!(contract.revoke_vesting(first), Ok(()));
+            assert!(contract.get_underfunded_beneficiaries().is_empty());
+            assert_eq!(contract.reconcile(), (2_000_000, 2_000_000, true));
+            ink::env::test::set_contract_balance(contract_address(), U256::from(1_999_999u128));
+            assert_eq!(contract.get_underfunded_beneficiaries(), vec![second]);
+            assert!(!contract.reconcile().2);
+        }
+
+        #[ink::test]
+        fn test_schedule_duration_days() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([40u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.schedule_duration_days(beneficiary), None);
+
+            // 100 days plus a few hours still counts as 100 whole days
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY + (5 * 60 * 60 * 1000);
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            assert_eq!(contract.schedule_duration_days(beneficiary), Some(100));
+
+            // Without a duration cap the day count can outgrow a u32; it saturates
+            let endless = H160::from([192u8; 20]);
+            let end = start + (u32::MAX as u64 + 5) * MS_PER_DAY;
+            create_schedule(&mut contract, endless, 1_000_000, start, end).unwrap();
+            assert_eq!(contract.schedule_duration_days(endless), Some(u32::MAX));
+        }
+
+        #[cfg(not(feature = "saturating-math"))]
+        #[ink::test]
+        fn test_vested_amount_overflow_reported() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([41u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // An enormous grant over ten years
+            let start = 1729512000000u64;
+            let end = start + 3650 * MS_PER_DAY;
+            let result = create_schedule(&mut contract, beneficiary, Balance::MAX - 1, start, end);
+            assert_eq!(result, Ok(()));
+
+            // total * elapsed no longer fits, which must not be silently clamped
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 365 * MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested(), Err(Error::Overflow));
+            // Views report the overflow instead of a misleading 0
+            let now = start + 365 * MS_PER_DAY;
+            assert_eq!(contract.vested_at(beneficiary, now), Err(Error::Overflow));
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, now),
+                Err(Error::Overflow)
+            );
+            assert_eq!(
+                contract.get_claimable_batch(vec![beneficiary]),
+                vec![Err(Error::Overflow)]
+            );
+
+            // Once fully vested no multiplication is needed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            assert_eq!(contract.claim_vested(), Ok(Balance::MAX - 1));
+        }
+
+        #[ink::test]
+        fn test_beneficiaries_paged() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + (100 * 24 * 60 * 60 * 1000);
+            let beneficiaries: Vec<H160> = (50..55u8).map(|i| H160::from([i; 20])).collect();
+            for beneficiary in &beneficiaries {
+                let result = create_schedule(&mut contract, *beneficiary, 1_000_000, start, end);
+                assert_eq!(result, Ok(()));
+            }
+
+            // First page
+            assert_eq!(
+                contract.get_beneficiaries_paged(0, 2),
+                beneficiaries[0..2].to_vec()
+            );
+            // Middle page
+            assert_eq!(
+                contract.get_beneficiaries_paged(2, 2),
+                beneficiaries[2..4].to_vec()
+            );
+            // Last, partial page
+            assert_eq!(
+                contract.get_beneficiaries_paged(4, 2),
+                beneficiaries[4..].to_vec()
+            );
+            // Oversized limits are clamped rather than rejected
+            assert_eq!(contract.get_beneficiaries_paged(0, u32::MAX), beneficiaries);
+            // Out of range
+            assert!(contract.get_beneficiaries_paged(5, 2).is_empty());
+            assert!(contract.get_beneficiaries_paged(u32::MAX, 2).is_empty());
+
+            // Removing an address keeps the others in creation order
+            assert_eq!(contract.cancel_pending_vesting(beneficiaries[1]), Ok(()));
+            assert_eq!(
+                contract.get_beneficiaries_paged(1, 2),
+                beneficiaries[2..4].to_vec()
+            );
+            assert_eq!(contract.get_all_beneficiaries().len(), 4);
+        }
+
+        #[ink::test]
+        fn test_vesting_created_readable_event() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([42u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            contract
+                .set_event_verbosity(EventVerbosity::Verbose)
+                .unwrap();
+
+            let start = 1729512000000u64; // 2024-10-21 12:00:00
+            let end = 1737374400000u64; // 2025-01-20 12:00:00
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            let event: VestingCreatedReadable = last_event();
+            assert_eq!(event.beneficiary, beneficiary);
+            assert_eq!(event.total_amount, 1_000_000);
+            assert_eq!(&event.start_readable[..], b"2024-10-21 12:00:00");
+            assert_eq!(&event.end_readable[..], b"2025-01-20 12:00:00");
+        }
+
+        #[ink::test]
+        fn test_min_claim_interval() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([43u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            // One claim per day at most
+            assert_eq!(contract.set_min_claim_interval(MS_PER_DAY), Ok(()));
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            ink::env::test::set_caller(beneficiary);
+            let first_claim = start + 10 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(first_claim);
+            assert_eq!(contract.claim_vested(), Ok(100_000));
+
+            // A rapid second claim is blocked
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                first_claim + MS_PER_DAY - 1,
+            );
+            assert_eq!(contract.claim_vested(), Err(Error::ClaimTooSoon));
+
+            // Allowed once the interval has elapsed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                first_claim + MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested(), Ok(10_000));
+        }
+
+        #[ink::test]
+        fn test_extend_vesting() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([44u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            // Half-way through, stretch the grant by another 50 days
+            let midpoint = start + 50 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(midpoint);
+            let new_end = end + 50 * MS_PER_DAY;
+            assert_eq!(contract.extend_vesting(beneficiary, new_end), Ok(()));
+
+            // Already-vested tokens remain claimable
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.claim_vested(), Ok(500_000));
+
+            // The remaining 500k now vests over 100 days instead of 50
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            assert_eq!(contract.claim_vested(), Ok(250_000));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(new_end);
+            assert_eq!(contract.claim_vested(), Ok(250_000));
+        }
+
+        #[ink::test]
+        fn test_extend_vesting_rejects_earlier_end() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([45u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            assert_eq!(
+                contract.extend_vesting(beneficiary, end),
+                Err(Error::InvalidTimeRange)
+            );
+            assert_eq!(
+                contract.extend_vesting(beneficiary, end - 1),
+                Err(Error::InvalidTimeRange)
+            );
+
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(
+                contract.extend_vesting(beneficiary, end + 1),
+                Err(Error::Unauthorized)
+            );
+        }
+
+        // Key pair with private key [0x42; 32]; the signature covers
+        // keccak256(beneficiary ++ 0u64 (LE) ++ [0xCC; 20]) with the contract at [0xCC; 20]
+        const META_BENEFICIARY: [u8; 20] = [
+            0x17, 0xc5, 0x18, 0x51, 0x67, 0x40, 0x1e, 0xd0, 0x0c, 0xf5, 0xf5, 0xb2, 0xfc, 0x97,
+            0xd9, 0xbb, 0xfd, 0xb7, 0xd0, 0x25,
+        ];
+        const META_SIGNATURE_NONCE_0: [u8; 65] = [
+            0xe8, 0x42, 0x60, 0x2c, 0xd1, 0x0f, 0x24, 0x58, 0xcc, 0xde, 0xbb, 0x40, 0x34, 0x95,
+            0xe2, 0x2b, 0x13, 0xca, 0x70, 0xbf, 0xc2, 0x33, 0x6a, 0x43, 0x11, 0xaf, 0xfd, 0x7d,
+            0x42, 0x81, 0x7c, 0x90, 0x7a, 0x01, 0x4a, 0xab, 0x7e, 0x34, 0xc9, 0xf8, 0x6d, 0x86,
+            0x7c, 0xcf, 0xeb, 0xe1, 0x5c, 0x70, 0x9f, 0x8d, 0x20, 0x5d, 0xfa, 0xc8, 0xb0, 0x9e,
+            0xa2, 0x87, 0x0d, 0x0e, 0x52, 0x80, 0xfc, 0xe3, 0x01,
+        ];
+
+        /// Deploys at the address the meta-claim signature was made for and creates a grant
+        fn setup_meta_claim() -> (VestingScheduler, H160) {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from(META_BENEFICIARY);
+
+            ink::env::test::set_callee(H160::from([0xCC; 20]));
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+
+            (contract, beneficiary)
+        }
+
+        #[ink::test]
+        fn test_claim_vested_meta_with_valid_signature() {
+            let (mut contract, beneficiary) = setup_meta_claim();
+
+            // Submitted by a relayer, not the beneficiary
+            ink::env::test::set_caller(H160::from([46u8; 20]));
+            assert_eq!(
+                contract.claim_vested_meta(beneficiary, 0, META_SIGNATURE_NONCE_0),
+                Ok(1_000_000)
+            );
+            assert_eq!(contract.get_nonce(beneficiary), 1);
+
+            // Replaying the same signature fails
+            assert_eq!(
+                contract.claim_vested_meta(beneficiary, 0, META_SIGNATURE_NONCE_0),
+                Err(Error::BadNonce)
+            );
+        }
+
+        #[ink::test]
+        fn test_claim_vested_meta_rejects_bad_nonce_and_signature() {
+            let (mut contract, beneficiary) = setup_meta_claim();
+            ink::env::test::set_caller(H160::from([47u8; 20]));
+
+            // The signature is only valid for nonce 0
+            assert_eq!(
+                contract.claim_vested_meta(beneficiary, 1, META_SIGNATURE_NONCE_0),
+                Err(Error::BadNonce)
+            );
+
+            // A signature from anyone else doesn't authorize the claim
+            let mut forged = META_SIGNATURE_NONCE_0;
+            forged[10] ^= 0xff;
+            assert_eq!(
+                contract.claim_vested_meta(beneficiary, 0, forged),
+                Err(Error::InvalidSignature)
+            );
+
+            // A valid signature for one beneficiary can't claim for another
+            let other = H160::from([48u8; 20]);
+            assert_eq!(
+                contract.claim_vested_meta(other, 0, META_SIGNATURE_NONCE_0),
+                Err(Error::InvalidSignature)
+            );
+            assert_eq!(contract.get_nonce(beneficiary), 0);
+        }
+
+        #[ink::test]
+        fn test_next_unlock_time_stepped() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([49u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // Four quarterly tranches over 100 days: days 25, 50, 75 and 100
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            let kind = VestingKind::Stepped { intervals: 4 };
+            let result = contract.create_vesting_schedule_with_kind(
+                beneficiary,
+                1_000_000,
+                start,
+                end,
+                kind,
+            );
+            assert_eq!(result, Ok(()));
+
+            // Before start the first boundary is next
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start - 1);
+            assert_eq!(
+                contract.next_unlock_time(beneficiary),
+                Some(start + 25 * MS_PER_DAY)
+            );
+
+            // Day 30: one tranche is out, the next one unlocks on day 50
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 30 * MS_PER_DAY,
+            );
+            assert_eq!(
+                contract.next_unlock_time(beneficiary),
+                Some(start + 50 * MS_PER_DAY)
+            );
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.claim_vested(), Ok(250_000));
+
+            // Exactly on a boundary the following one is next
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 75 * MS_PER_DAY,
+            );
+            assert_eq!(contract.next_unlock_time(beneficiary), Some(end));
+            assert_eq!(contract.claim_vested(), Ok(500_000));
+
+            // Fully vested
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            assert_eq!(contract.next_unlock_time(beneficiary), None);
+        }
+
+        #[ink::test]
+        fn test_next_unlock_time_linear_is_continuous() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([51u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.next_unlock_time(beneficiary), None);
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            let result = create_schedule(&mut contract, beneficiary, 1_000_000, start, end);
+            assert_eq!(result, Ok(()));
+
+            let now = start + 10 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+            assert_eq!(contract.next_unlock_time(beneficiary), Some(now));
+
+            // Stepped schedules need at least one interval
+            let kind = VestingKind::Stepped { intervals: 0 };
+            let result = contract.create_vesting_schedule_with_kind(
+                H160::from([52u8; 20]),
+                1_000_000,
+                start,
+                end,
+                kind,
+            );
+            assert_eq!(result, Err(Error::InvalidIntervals));
+        }
+
+        #[ink::test]
+        fn test_max_duration() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([53u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            // Cap grants at 10 years
+            assert_eq!(contract.set_max_duration(3650 * MS_PER_DAY), Ok(()));
+
+            let start = 1729512000000u64;
+
+            // A 4-year grant is fine
+            let result = create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 4 * 365 * MS_PER_DAY,
+            );
+            assert_eq!(result, Ok(()));
+
+            // A typo turning it into 100 years is rejected
+            let result = create_schedule(
+                &mut contract,
+                H160::from([181u8; 20]),
+                1_000_000,
+                start,
+                start + 100 * 365 * MS_PER_DAY,
+            );
+            assert_eq!(result, Err(Error::DurationTooLong));
+        }
+
+        #[ink::test]
+        fn test_simulate_claimable_at() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([55u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            let end = start + 100 * MS_PER_DAY;
+            create_schedule(&mut contract, beneficiary, 1_000_000, start, end).unwrap();
+
+            // Three quarters through the grant, without moving the clock
+            let projected = contract.simulate_claimable_at(beneficiary, start + 75 * MS_PER_DAY);
+            assert_eq!(projected, Ok(750_000));
+            assert_eq!(
+                contract.simulate_claimable_at(H160::from([56u8; 20]), end),
+                Err(Error::NoVestingSchedule)
+            );
+
+            // Claimed tokens are subtracted from the projection
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 50 * MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested(), Ok(500_000));
+            let projected = contract.simulate_claimable_at(beneficiary, start + 75 * MS_PER_DAY);
+            assert_eq!(projected, Ok(250_000));
+        }
+
+        #[ink::test]
+        fn test_renounce_ownership() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([57u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // Only the owner may renounce
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.renounce_ownership(), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller(owner);
+            assert_eq!(contract.renounce_ownership(), Ok(()));
+            let event: OwnershipTransferred = last_event();
+            assert_eq!(event.previous_owner, owner);
+            assert_eq!(event.new_owner, H160::zero());
+
+            // Admin functions are locked for good
+            let start = 1729512000000u64;
+            let result =
+                create_schedule(&mut contract, beneficiary, 1_000, start, start + MS_PER_DAY);
+            assert_eq!(result, Err(Error::Unauthorized));
+            assert_eq!(contract.renounce_ownership(), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn test_remaining_balance() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([58u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.remaining_balance(beneficiary), None);
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            assert_eq!(contract.remaining_balance(beneficiary), Some(1_000_000));
+
+            // Claim 30% after 30 days
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 30 * MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested(), Ok(300_000));
+            assert_eq!(contract.remaining_balance(beneficiary), Some(700_000));
+        }
+
+        #[ink::test]
+        fn test_min_duration() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([59u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            let start = 1729512000000u64;
+
+            // Without a floor even a one-millisecond grant is accepted
+            let other: H160 = H160::from([60u8; 20]);
+            assert_eq!(
+                create_schedule(&mut contract, other, 1_000, start, start + 1),
+                Ok(())
+            );
+
+            // Require at least one hour
+            assert_eq!(contract.set_min_duration(60 * 60 * 1000), Ok(()));
+            let result = create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000,
+                start,
+                start + 59 * 60 * 1000,
+            );
+            assert_eq!(result, Err(Error::DurationTooShort));
+
+            let result =
+                create_schedule(&mut contract, beneficiary, 1_000, start, start + MS_PER_DAY);
+            assert_eq!(result, Ok(()));
+        }
+
+        #[ink::test]
+        fn test_two_admin_approval() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let first_admin: H160 = accounts.bob.into();
+            let second_admin: H160 = accounts.charlie.into();
+            let beneficiary: H160 = H160::from([61u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            contract.set_admin(first_admin, true).unwrap();
+            contract.set_admin(second_admin, true).unwrap();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+
+            // Non-admins can't propose
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(
+                contract.propose_vesting(beneficiary, 1_000_000, start, start + MS_PER_DAY),
+                Err(Error::Unauthorized)
+            );
+
+            ink::env::test::set_caller(first_admin);
+            let id = contract
+                .propose_vesting(beneficiary, 1_000_000, start, start + 100 * MS_PER_DAY)
+                .unwrap();
+            assert_eq!(contract.approve_vesting(id), Ok(()));
+            assert_eq!(contract.approve_vesting(id), Err(Error::AlreadyApproved));
+            // One signature is not enough
+            assert!(!contract.has_schedule(beneficiary));
+
+            ink::env::test::set_caller(second_admin);
+            assert_eq!(contract.approve_vesting(id), Ok(()));
+            assert!(contract.has_schedule(beneficiary));
+            assert!(contract.get_proposal(id).is_none());
+            assert_eq!(contract.approve_vesting(id), Err(Error::ProposalNotFound));
+
+            // Readable events are off at the default verbosity
+            let created: VestingCreated = last_event();
+            assert_eq!(created.beneficiary, beneficiary);
+            assert_eq!(created.total_amount, 1_000_000);
+        }
+
+        #[ink::test]
+        fn test_datetime_round_trip() {
+            let contract = VestingScheduler::new();
+
+            for timestamp in [
+                0u64,
+                951_782_400_000,   // 2000-02-29 00:00:00 (leap day)
+                1_729_512_000_000, // 2024-10-21 12:00:00
+                1_735_689_599_000, // 2024-12-31 23:59:59
+                4_107_542_400_000, // 2100-03-01 00:00:00 (not a leap year)
+                MAX_SUPPORTED_TIMESTAMP_MS - 999,
+            ] {
+                let dt = contract.timestamp_to_datetime(timestamp);
+                assert_eq!(contract.datetime_to_timestamp(dt), Ok(timestamp));
+            }
+        }
+
+        #[ink::test]
+        fn test_create_vesting_schedule_from_dates() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([62u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = DateTime {
+                year: 2024,
+                month: 10,
+                day: 21,
+                hour: 12,
+                minute: 0,
+                second: 0,
+                millisecond: 0,
+            };
+            let end = DateTime {
+                year: 2025,
+                month: 1,
+                day: 29,
+                hour: 12,
+                minute: 0,
+                second: 0,
+                millisecond: 0,
+            };
+            assert_eq!(
+                contract.create_vesting_schedule_from_dates(beneficiary, 1_000_000, start, end),
+                Ok(())
+            );
+
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.start_time, 1729512000000);
+            assert_eq!(schedule.end_time, 1729512000000 + 100 * MS_PER_DAY);
+        }
+
+        #[ink::test]
+        fn test_get_claimable_batch() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let first: H160 = H160::from([63u8; 20]);
+            let second: H160 = H160::from([64u8; 20]);
+            let missing: H160 = H160::from([65u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                first,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            create_schedule(&mut contract, second, 2_000, start, start + 10 * MS_PER_DAY).unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 20 * MS_PER_DAY,
+            );
+            let amounts = contract.get_claimable_batch(vec![first, missing, second]);
+            assert_eq!(
+                amounts,
+                vec![Ok(200_000), Err(Error::NoVestingSchedule), Ok(2_000)]
+            );
+
+            // Oversized requests are truncated
+            let amounts = contract.get_claimable_batch(vec![first; 250]);
+            assert_eq!(amounts.len(), MAX_BATCH_SIZE as usize);
+        }
+
+        #[ink::test]
+        fn test_claim_vested_capped() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([66u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            // 400k available; drip out half of it
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 40 * MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested_capped(200_000), Ok(200_000));
+
+            // A cap above what's available only takes the remainder
+            assert_eq!(contract.claim_vested_capped(1_000_000), Ok(200_000));
+            assert_eq!(
+                contract.claim_vested_capped(1_000_000),
+                Err(Error::NoTokensAvailable)
+            );
+            assert_eq!(
+                contract
+                    .get_vesting_schedule(beneficiary)
+                    .unwrap()
+                    .claimed_amount,
+                400_000
+            );
+        }
+
+        #[ink::test]
+        fn test_revoke_vesting() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([67u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            assert!(
+                contract
+                    .get_vesting_schedule(beneficiary)
+                    .unwrap()
+                    .revocable
+            );
+
+            // Revoke halfway through
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 50 * MS_PER_DAY,
+            );
+            assert_eq!(contract.revoke_vesting(beneficiary), Ok(()));
+            let event: VestingRevoked = last_event();
+            assert_eq!(event.vested_amount, 500_000);
+            assert_eq!(event.unvested_amount, 500_000);
+            assert_eq!(
+                contract.revoke_vesting(beneficiary),
+                Err(Error::AlreadyRevoked)
+            );
+
+            // The vested half can still be claimed, nothing more accrues
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 100 * MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested(), Ok(500_000));
+            assert_eq!(contract.claim_vested(), Err(Error::NoTokensAvailable));
+        }
+
+        #[ink::test]
+        fn test_irrevocable_grant_cannot_be_revoked() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([68u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            assert_eq!(contract.make_irrevocable(beneficiary), Ok(()));
+
+            assert_eq!(
+                contract.revoke_vesting(beneficiary),
+                Err(Error::NotRevocable)
+            );
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert!(!schedule.revoked);
+            assert_eq!(schedule.total_amount, 1_000_000);
+        }
+
+        #[ink::test]
+        fn test_irrevocable_at_creation_cannot_be_replaced() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([180u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            let params = ScheduleParams {
+                revocable: false,
+                ..Default::default()
+            };
+            assert_eq!(
+                contract.create_vesting_schedule(beneficiary, 1_000_000, start, end, params),
+                Ok(())
+            );
+            assert!(
+                !contract
+                    .get_vesting_schedule(beneficiary)
+                    .unwrap()
+                    .revocable
+            );
+            assert_eq!(
+                contract.revoke_vesting(beneficiary),
+                Err(Error::NotRevocable)
+            );
+
+            // No creation path can overwrite the grant with a smaller one
+            assert_eq!(
+                create_schedule(&mut contract, beneficiary, 1, start, end),
+                Err(Error::ScheduleAlreadyExists)
+            );
+            assert_eq!(
+                contract.validate_vesting_params(beneficiary, 1, start, end),
+                Err(Error::ScheduleAlreadyExists)
+            );
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.total_amount, 1_000_000);
+            assert_eq!(contract.total_outstanding(), 1_000_000);
+        }
+
+        #[ink::test]
+        fn test_vesting_progress_bps() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([69u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.vesting_progress_bps(beneficiary), None);
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, beneficiary, 1_000_000, start, end).unwrap();
+            assert_eq!(contract.vesting_progress_bps(beneficiary), Some(0));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 50 * MS_PER_DAY,
+            );
+            assert_eq!(contract.vesting_progress_bps(beneficiary), Some(5_000));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end + MS_PER_DAY);
+            assert_eq!(contract.vesting_progress_bps(beneficiary), Some(10_000));
+        }
+
+        #[ink::test]
+        fn test_claim_recipient_topic() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([70u8; 20]);
+            let cold_wallet: H160 = H160::from([71u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            // A plain claim goes to the beneficiary
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 10 * MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested(), Ok(100_000));
+            let event: TokensClaimed = last_event();
+            assert_eq!(event.beneficiary, beneficiary);
+            assert_eq!(event.recipient, beneficiary);
+
+            // A redirected claim topics the alternate recipient
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 20 * MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested_to(cold_wallet), Ok(100_000));
+            let event: TokensClaimed = last_event();
+            assert_eq!(event.beneficiary, beneficiary);
+            assert_eq!(event.recipient, cold_wallet);
+            assert_eq!(event.amount, 100_000);
+        }
+
+        #[ink::test]
+        fn test_total_outstanding() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let first: H160 = H160::from([72u8; 20]);
+            let second: H160 = H160::from([73u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.total_outstanding(), 0);
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, first, 1_000_000, start, end).unwrap();
+            create_schedule(&mut contract, second, 500_000, start, end).unwrap();
+            assert_eq!(contract.total_outstanding(), 1_500_000);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 50 * MS_PER_DAY,
+            );
+            ink::env::test::set_caller(first);
+            contract.claim_vested().unwrap();
+            assert_eq!(contract.total_outstanding(), 1_000_000);
+
+            ink::env::test::set_caller(second);
+            contract.claim_vested().unwrap();
+            assert_eq!(contract.total_outstanding(), 750_000);
+
+            // Revoking cancels the unvested half of the second grant
+            ink::env::test::set_caller(owner);
+            contract.revoke_vesting(second).unwrap();
+            assert_eq!(contract.total_outstanding(), 500_000);
+        }
+
+        #[ink::test]
+        fn test_zero_start_time_means_now() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([74u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let now = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                0,
+                now + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            assert_eq!(
+                contract
+                    .get_vesting_schedule(beneficiary)
+                    .unwrap()
+                    .start_time,
+                now
+            );
+
+            // Vesting is already running
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now + MS_PER_DAY);
+            assert_eq!(contract.claim_vested(), Ok(10_000));
+        }
+
+        #[ink::test]
+        fn test_get_owner() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.get_owner(), owner);
+
+            contract.renounce_ownership().unwrap();
+            assert_eq!(contract.get_owner(), H160::zero());
+        }
+
+        #[ink::test]
+        fn test_many_small_claims_leave_no_dust() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([75u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // An amount and duration that don't divide evenly
+            let total = 1_000_003;
+            let start = 1729512000000u64;
+            let end = start + 7 * MS_PER_DAY + 13;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, beneficiary, total, start, end).unwrap();
+
+            // Claim at awkward intervals, each truncating a little
+            ink::env::test::set_caller(beneficiary);
+            let mut received = 0;
+            let mut now = start;
+            while now < end {
+                now += 3_333_331;
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+                if let Ok(amount) = contract.claim_vested() {
+                    received += amount;
+                }
+            }
+
+            // The final claim at or after end_time sweeps up the dust
+            assert_eq!(received, total);
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.claimed_amount, schedule.total_amount);
+        }
+
+        #[ink::test]
+        fn test_claim_count() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([76u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            assert_eq!(contract.get_claim_count(beneficiary), 0);
+
+            ink::env::test::set_caller(beneficiary);
+            for day in 1..=3 {
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                    start + day * MS_PER_DAY,
+                );
+                contract.claim_vested().unwrap();
+                assert_eq!(contract.get_claim_count(beneficiary), day as u32);
+            }
+
+            // Failed claims don't count
+            assert_eq!(contract.claim_vested(), Err(Error::NoTokensAvailable));
+            assert_eq!(contract.get_claim_count(beneficiary), 3);
+        }
+
+        #[ink::test]
+        fn test_prune_schedule() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let finished: H160 = H160::from([77u8; 20]);
+            let ongoing: H160 = H160::from([78u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, finished, 1_000, start, end).unwrap();
+            create_schedule(&mut contract, ongoing, 1_000, start, end).unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            ink::env::test::set_caller(finished);
+            contract.claim_vested().unwrap();
+
+            // Only the owner or the beneficiary may prune
+            ink::env::test::set_caller(ongoing);
+            assert_eq!(contract.prune_schedule(finished), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller(finished);
+            assert_eq!(contract.prune_schedule(finished), Ok(()));
+            let event: SchedulePruned = last_event();
+            assert_eq!(event.beneficiary, finished);
+            assert!(!contract.has_schedule(finished));
+            assert_eq!(contract.get_all_beneficiaries(), vec![ongoing]);
+            assert!(contract.get_claim_history(finished).is_empty());
+        }
+
+        #[ink::test]
+        fn test_prune_schedule_rejects_unclaimed() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([79u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 50 * MS_PER_DAY,
+            );
+            ink::env::test::set_caller(beneficiary);
+            contract.claim_vested().unwrap();
+
+            ink::env::test::set_caller(owner);
+            assert_eq!(
+                contract.prune_schedule(beneficiary),
+                Err(Error::NotFullyClaimed)
+            );
+            assert!(contract.has_schedule(beneficiary));
+        }
+
+        #[ink::test]
+        fn test_periodic_vesting_with_partial_final_period() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([80u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // 30-day "months" over 100 days: 3 full periods plus 10 days
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            let month = 30 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            assert_eq!(
+                contract.create_periodic_vesting(beneficiary, 1_000_000, start, end, month),
+                Ok(())
+            );
+            assert_eq!(contract.next_unlock_time(beneficiary), Some(start + month));
+
+            let at = |days: u64| {
+                contract
+                    .simulate_claimable_at(beneficiary, start + days * MS_PER_DAY)
+                    .unwrap()
+            };
+            assert_eq!(at(29), 0);
+            assert_eq!(at(30), 300_000);
+            assert_eq!(at(59), 300_000);
+            assert_eq!(at(95), 900_000);
+            // The partial period unlocks at end time
+            assert_eq!(at(100), 1_000_000);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 95 * MS_PER_DAY,
+            );
+            assert_eq!(contract.next_unlock_time(beneficiary), Some(end));
+        }
+
+        #[ink::test]
+        fn test_periodic_vesting_rejects_zero_period() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([81u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let result =
+                contract.create_periodic_vesting(beneficiary, 1_000, start, start + MS_PER_DAY, 0);
+            assert_eq!(result, Err(Error::InvalidIntervals));
+        }
+
+        #[ink::test]
+        fn test_format_datetime_clamps_wide_years() {
+            let contract = VestingScheduler::new();
+            let dt = DateTime {
+                year: 12345,
+                month: 6,
+                day: 15,
+                hour: 8,
+                minute: 30,
+                second: 0,
+                millisecond: 0,
+            };
+            // Rendered as 9999 rather than wrapping to 2345
+            assert_eq!(&contract.format_datetime(dt), b"9999-06-15 08:30:00");
+        }
+
+        #[ink::test]
+        fn test_claim_vested_detailed() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([82u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let total = 1_000_000;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                total,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            ink::env::test::set_caller(beneficiary);
+            let mut claimed_total = 0;
+            for day in [25, 60] {
+                ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                    start + day * MS_PER_DAY,
+                );
+                let (claimed, remaining) = contract.claim_vested_detailed().unwrap();
+                claimed_total += claimed;
+                assert_eq!(remaining, total - claimed_total);
+            }
+            assert_eq!(claimed_total, 600_000);
+        }
+
+        #[ink::test]
+        fn test_format_amount() {
+            let contract = VestingScheduler::new_with_config(TimestampUnit::Millis, 6, false);
+            assert_eq!(contract.get_decimals(), 6);
+
+            let render = |value: Balance| {
+                let (buf, len) = contract.format_amount(value);
+                buf[..len as usize].to_vec()
+            };
+            assert_eq!(render(1_000_000), b"1.000000".to_vec());
+            assert_eq!(render(1_234_567_890), b"1234.567890".to_vec());
+            assert_eq!(render(5), b"0.000005".to_vec());
+            assert_eq!(render(0), b"0.000000".to_vec());
+
+            // Without decimals the integer is rendered as is
+            let contract = VestingScheduler::new_with_config(TimestampUnit::Millis, 0, false);
+            let (buf, len) = contract.format_amount(Balance::MAX);
+            assert_eq!(
+                &buf[..len as usize],
+                b"340282366920938463463374607431768211455"
+            );
+        }
+
+        #[ink::test]
+        fn test_event_verbosity_levels() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([83u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            let event_count = || ink::env::test::recorded_events().count();
+
+            // Verbose: TokensClaimed plus its readable twin
+            assert_eq!(contract.get_event_verbosity(), EventVerbosity::Standard);
+            assert_eq!(
+                contract.set_event_verbosity(EventVerbosity::Verbose),
+                Ok(())
+            );
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 10 * MS_PER_DAY,
+            );
+            let before = event_count();
+            contract.claim_vested().unwrap();
+            assert_eq!(event_count() - before, 2);
+
+            // Standard: only TokensClaimed
+            ink::env::test::set_caller(owner);
+            assert_eq!(
+                contract.set_event_verbosity(EventVerbosity::Standard),
+                Ok(())
+            );
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 20 * MS_PER_DAY,
+            );
+            let before = event_count();
+            contract.claim_vested().unwrap();
+            assert_eq!(event_count() - before, 1);
+            let event: TokensClaimed = last_event();
+            assert_eq!(event.amount, 100_000);
+            // 100k is in the thousands bucket
+            assert_eq!(event.amount_bucket, 1);
+        }
+
+        #[ink::test]
+        fn test_event_verbosity_none_is_silent() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([114u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.set_event_verbosity(EventVerbosity::None), Ok(()));
+            let before = ink::env::test::recorded_events().count();
+
+            // Create, claim with a fee, revoke, withdraw and renounce: no events at all
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            contract.set_claim_fee_bps(100).unwrap();
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 10 * MS_PER_DAY,
+            );
+            contract.claim_vested().unwrap();
+            ink::env::test::set_caller(owner);
+            ink::env::test::set_contract_balance(contract_address(), U256::from(1_000_000u128));
+            contract.revoke_vesting(beneficiary).unwrap();
+            contract.withdraw_reclaimed().unwrap();
+            contract.renounce_ownership().unwrap();
+
+            assert_eq!(ink::env::test::recorded_events().count(), before);
+        }
+
+        #[ink::test]
+        fn test_withdraw_reclaimed() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([84u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            ink::env::test::set_contract_balance(contract_address(), U256::from(1_000_000u128));
+            assert_eq!(contract.withdraw_reclaimed(), Err(Error::NoTokensAvailable));
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            // Revoke at the halfway mark
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 50 * MS_PER_DAY,
+            );
+            contract.revoke_vesting(beneficiary).unwrap();
+            assert_eq!(contract.get_reclaimable(), 500_000);
+
+            // Only the owner may withdraw
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.withdraw_reclaimed(), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller(owner);
+            assert_eq!(contract.withdraw_reclaimed(), Ok(500_000));
+            let event: ReclaimedWithdrawn = last_event();
+            assert_eq!(event.recipient, owner);
+            assert_eq!(event.amount, 500_000);
+            assert_eq!(contract.get_reclaimable(), 0);
+        }
+
+        #[ink::test]
+        fn test_zero_beneficiary_rejected() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let result = create_schedule(
+                &mut contract,
+                H160::zero(),
+                1_000,
+                start,
+                start + MS_PER_DAY,
+            );
+            assert_eq!(result, Err(Error::InvalidBeneficiary));
+            assert!(contract.get_all_beneficiaries().is_empty());
+        }
+
+        #[ink::test]
+        fn test_zero_amount_rejected() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([85u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let result = create_schedule(&mut contract, beneficiary, 0, start, start + MS_PER_DAY);
+            assert_eq!(result, Err(Error::InvalidAmount));
+            assert!(!contract.has_schedule(beneficiary));
+        }
+
+        #[ink::test]
+        fn test_beneficiaries_ending_between() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let soon: H160 = H160::from([86u8; 20]);
+            let later: H160 = H160::from([87u8; 20]);
+            let much_later: H160 = H160::from([88u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            create_schedule(&mut contract, soon, 1_000, start, start + 30 * MS_PER_DAY).unwrap();
+            create_schedule(&mut contract, later, 1_000, start, start + 60 * MS_PER_DAY).unwrap();
+            create_schedule(
+                &mut contract,
+                much_later,
+                1_000,
+                start,
+                start + 365 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            let window = contract
+                .beneficiaries_ending_between(start + 45 * MS_PER_DAY, start + 90 * MS_PER_DAY);
+            assert_eq!(window, vec![later]);
+
+            // Bounds are inclusive
+            let window = contract
+                .beneficiaries_ending_between(start + 30 * MS_PER_DAY, start + 60 * MS_PER_DAY);
+            assert_eq!(window, vec![soon, later]);
+        }
+
+        #[ink::test]
+        fn test_format_datetime_millis() {
+            let contract = VestingScheduler::new();
+
+            // 2024-10-21 12:00:00.123
+            let timestamp = 1_729_512_000_123u64;
+            let dt = contract.timestamp_to_datetime(timestamp);
+            assert_eq!(dt.millisecond, 123);
+            assert_eq!(
+                &contract.format_datetime_millis(dt),
+                b"2024-10-21 12:00:00.123"
+            );
+
+            // Milliseconds survive the round trip
+            let dt = contract.timestamp_to_datetime(timestamp);
+            assert_eq!(contract.datetime_to_timestamp(dt), Ok(timestamp));
+
+            // Readable claim events carry them too
+            let accounts = ink::env::test::default_accounts();
+            let beneficiary = H160::from([168u8; 20]);
+            ink::env::test::set_caller(accounts.alice.into());
+            let mut contract = VestingScheduler::new();
+            contract
+                .set_event_verbosity(EventVerbosity::Verbose)
+                .unwrap();
+            let start = 1_729_512_000_000u64;
+            create_schedule(&mut contract, beneficiary, 1_000, start, start + 1_000).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(timestamp);
+            ink::env::test::set_caller(beneficiary);
+            contract.claim_vested().unwrap();
+            let event = last_event::<TokensClaimedReadable>();
+            assert_eq!(&event.claimed_at_readable, b"2024-10-21 12:00:00.123");
+        }
+
+        #[ink::test]
+        fn test_revoke_vesting_batch() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let first: H160 = H160::from([89u8; 20]);
+            let second: H160 = H160::from([90u8; 20]);
+            let kept: H160 = H160::from([91u8; 20]);
+            let missing: H160 = H160::from([92u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            for beneficiary in [first, second, kept] {
+                create_schedule(&mut contract, beneficiary, 1_000, start, end).unwrap();
+            }
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 50 * MS_PER_DAY,
+            );
+            let before = ink::env::test::recorded_events().count();
+            // Duplicates and unknown addresses are skipped
+            let revoked = contract.revoke_vesting_batch(vec![first, missing, second, first]);
+            assert_eq!(revoked, Ok(2));
+            // ScheduleTimeChanged and VestingRevoked per revocation
+            assert_eq!(ink::env::test::recorded_events().count() - before, 4);
+
+            assert!(contract.get_vesting_schedule(first).unwrap().revoked);
+            assert!(contract.get_vesting_schedule(second).unwrap().revoked);
+            assert!(!contract.get_vesting_schedule(kept).unwrap().revoked);
+            assert_eq!(contract.get_reclaimable(), 1_000);
+        }
+
+        #[ink::test]
+        fn test_current_block_time() {
+            let contract = VestingScheduler::new();
+
+            // Oct 21, 2024, 12:00:00 UTC
+            let now = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now);
+            assert_eq!(contract.current_block_time(), now);
+            assert_eq!(
+                contract.current_block_time_readable(),
+                Some(*b"2024-10-21 12:00:00")
+            );
+
+            // Seconds-based chains report their raw value
+            let contract =
+                VestingScheduler::new_with_config(TimestampUnit::Seconds, DEFAULT_DECIMALS, false);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now / 1000);
+            assert_eq!(contract.current_block_time(), now / 1000);
+            assert_eq!(
+                contract.current_block_time_readable(),
+                Some(*b"2024-10-21 12:00:00")
+            );
+
+            // A clock past year 9999 can't be rendered in four digits
+            let contract = VestingScheduler::new();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                MAX_SUPPORTED_TIMESTAMP_MS + 1,
+            );
+            assert_eq!(contract.current_block_time_readable(), None);
+        }
+
+        #[ink::test]
+        fn test_fund_tracks_total() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let treasury: H160 = accounts.bob.into();
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.get_total_funded(), 0);
+
+            ink::env::test::set_caller(treasury);
+            ink::env::test::set_value_transferred(U256::from(600_000u128));
+            assert_eq!(contract.fund(), Ok(600_000));
+
+            ink::env::test::set_value_transferred(U256::from(400_000u128));
+            assert_eq!(contract.fund(), Ok(1_000_000));
+            let event: FundsReceived = last_event();
+            assert_eq!(event.from, treasury);
+            assert_eq!(event.amount, 400_000);
+            assert_eq!(event.total_funded, 1_000_000);
+            assert_eq!(contract.get_total_funded(), 1_000_000);
+
+            // Empty deposits are rejected
+            ink::env::test::set_value_transferred(U256::zero());
+            assert_eq!(contract.fund(), Err(Error::InvalidAmount));
+        }
+
+        #[ink::test]
+        fn test_private_schedules() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([93u8; 20]);
+            let stranger: H160 = H160::from([94u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract =
+                VestingScheduler::new_with_config(TimestampUnit::Millis, DEFAULT_DECIMALS, true);
+            let start = 1729512000000u64;
+            create_schedule(&mut contract, beneficiary, 1_000, start, start + MS_PER_DAY).unwrap();
+
+            // The owner can still read every grant
+            assert!(contract.get_vesting_schedule(beneficiary).is_some());
+
+            // Third parties can't
+            ink::env::test::set_caller(stranger);
+            assert!(contract.get_vesting_schedule(beneficiary).is_none());
+            assert!(
+                contract
+                    .get_vesting_schedule_readable(beneficiary)
+                    .is_none()
+            );
+            assert!(contract.get_my_schedule().is_none());
+
+            // The beneficiary sees their own grant
+            ink::env::test::set_caller(beneficiary);
+            assert!(contract.get_vesting_schedule(beneficiary).is_some());
+            assert_eq!(contract.get_my_schedule().unwrap().total_amount, 1_000);
+        }
+
+        #[ink::test]
+        fn test_private_schedules_hide_derived_views() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([184u8; 20]);
+            let stranger = H160::from([185u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract =
+                VestingScheduler::new_with_config(TimestampUnit::Millis, DEFAULT_DECIMALS, true);
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, beneficiary, 1_000_000, start, end).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 10 * MS_PER_DAY,
+            );
+            ink::env::test::set_caller(beneficiary);
+            contract.claim_vested().unwrap();
+
+            // Nothing derived from the grant reaches a third party
+            ink::env::test::set_caller(stranger);
+            assert_eq!(
+                contract.vested_at(beneficiary, u64::MAX),
+                Err(Error::NoVestingSchedule)
+            );
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, end),
+                Err(Error::NoVestingSchedule)
+            );
+            assert_eq!(
+                contract.get_claimable_batch(vec![beneficiary]),
+                vec![Err(Error::NoVestingSchedule)]
+            );
+            assert_eq!(contract.remaining_balance(beneficiary), None);
+            assert!(contract.get_claim_history(beneficiary).is_empty());
+            assert_eq!(contract.total_claimed_by(beneficiary), 0);
+            assert_eq!(contract.time_for_amount(beneficiary, 1), None);
+            assert_eq!(contract.schedule_duration_days(beneficiary), None);
+            assert!(contract.get_beneficiaries_by_end_time().is_empty());
+            assert!(contract.beneficiaries_ending_between(start, end).is_empty());
+
+            // The owner still sees everything
+            ink::env::test::set_caller(owner);
+            assert_eq!(contract.vested_at(beneficiary, u64::MAX), Ok(1_000_000));
+            assert_eq!(contract.remaining_balance(beneficiary), Some(900_000));
+            assert_eq!(contract.get_claim_history(beneficiary).len(), 1);
+            assert_eq!(
+                contract.get_beneficiaries_by_end_time(),
+                vec![(beneficiary, end)]
+            );
+        }
+
+        #[ink::test]
+        fn test_public_schedules_by_default() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([95u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            let start = 1729512000000u64;
+            create_schedule(&mut contract, beneficiary, 1_000, start, start + MS_PER_DAY).unwrap();
+
+            ink::env::test::set_caller(H160::from([96u8; 20]));
+            assert!(contract.get_vesting_schedule(beneficiary).is_some());
+        }
+
+        #[ink::test]
+        fn test_min_fee_applies_to_tiny_claims() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([97u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            contract.set_claim_fee_bps(100).unwrap(); // 1%
+            contract.set_min_fee(10).unwrap();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                10_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            // 1% of a 50-token claim rounds to 0, so the floor applies
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + MS_PER_DAY / 2,
+            );
+            assert_eq!(contract.claim_vested(), Ok(40));
+            let event: FeeCollected = last_event();
+            assert_eq!(event.amount, 10);
+
+            // Large claims pay the percentage fee
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 100 * MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested(), Ok(9_950 - 99));
+        }
+
+        #[ink::test]
+        fn test_admin_action_audit_event() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([98u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            // Audit events are only emitted at the verbose level
+            contract
+                .set_event_verbosity(EventVerbosity::Verbose)
+                .unwrap();
+
+            // Contract-wide settings carry no target
+            contract.set_min_claim_interval(MS_PER_DAY).unwrap();
+            let event: AdminAction = last_event();
+            assert_eq!(event.action_kind, AdminActionKind::UpdateConfig as u8);
+            assert_eq!(event.caller, owner);
+            assert_eq!(event.target, H160::zero());
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, beneficiary, 1_000, start, start + MS_PER_DAY).unwrap();
+            // The audit entry precedes VestingCreated and its readable twin
+            let event: AdminAction = nth_last_event(2);
+            assert_eq!(event.action_kind, AdminActionKind::CreateSchedule as u8);
+            assert_eq!(event.target, beneficiary);
+
+            contract.revoke_vesting(beneficiary).unwrap();
+            // Followed by ScheduleTimeChanged and VestingRevoked
+            let event: AdminAction = nth_last_event(2);
+            assert_eq!(event.action_kind, AdminActionKind::RevokeSchedule as u8);
+            assert_eq!(event.target, beneficiary);
+
+            // Failed calls leave no audit entry
+            let before = ink::env::test::recorded_events().count();
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.set_min_claim_interval(0), Err(Error::Unauthorized));
+            assert_eq!(ink::env::test::recorded_events().count(), before);
+        }
+
+        #[ink::test]
+        fn test_sweep_untracked() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([99u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            // Exactly funded: nothing to sweep
+            ink::env::test::set_contract_balance(contract_address(), U256::from(1_000_000u128));
+            assert_eq!(contract.sweep_untracked(), Err(Error::NoTokensAvailable));
+
+            // Tokens sent directly to the contract are surplus
+            ink::env::test::set_contract_balance(contract_address(), U256::from(1_000_500u128));
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.sweep_untracked(), Err(Error::Unauthorized));
+            ink::env::test::set_caller(owner);
+            assert_eq!(contract.sweep_untracked(), Ok(500));
+            assert_eq!(contract.sweep_untracked(), Err(Error::NoTokensAvailable));
+        }
+
+        #[ink::test]
+        fn test_monthly_vesting_across_february() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([100u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // 2025-01-10 09:00:00, unlocking on the 31st for three months
+            let start = 1_736_499_600_000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            assert_eq!(
+                contract.create_monthly_vesting(beneficiary, 300_000, start, 3, 31),
+                Ok(())
+            );
+
+            // 2025-02-28, 2025-03-31 and 2025-04-30, all at 09:00:00
+            let feb_28 = 1_740_733_200_000u64;
+            let mar_31 = 1_743_411_600_000u64;
+            let apr_30 = 1_746_003_600_000u64;
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.end_time, apr_30);
+            assert_eq!(contract.next_unlock_time(beneficiary), Some(feb_28));
+
+            // Nothing on Jan 31: unlocks start the month after start
+            let jan_31 = 1_738_314_000_000u64;
+            assert_eq!(contract.simulate_claimable_at(beneficiary, jan_31), Ok(0));
+            // February has no 31st, so it unlocks on the 28th
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, feb_28 - 1),
+                Ok(0)
+            );
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, feb_28),
+                Ok(100_000)
+            );
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, mar_31 - 1),
+                Ok(100_000)
+            );
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, mar_31),
+                Ok(200_000)
+            );
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, apr_30),
+                Ok(300_000)
+            );
+        }
+
+        #[ink::test]
+        fn test_monthly_vesting_leap_year_and_validation() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([101u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // 2023-12-31 00:00:00, on the 30th for two months
+            let start = 1_703_980_800_000u64;
+            assert_eq!(
+                contract.create_monthly_vesting(beneficiary, 1_000, start, 2, 0),
+                Err(Error::InvalidTimeRange)
+            );
+            assert_eq!(
+                contract.create_monthly_vesting(beneficiary, 1_000, start, 0, 30),
+                Err(Error::InvalidIntervals)
+            );
+            assert_eq!(
+                contract.create_monthly_vesting(beneficiary, 1_000, start, 2, 30),
+                Ok(())
+            );
+
+            // 2024-01-30, then 2024-02-29 (leap year)
+            let jan_30 = 1_706_572_800_000u64;
+            let feb_29 = 1_709_164_800_000u64;
+            assert_eq!(contract.simulate_claimable_at(beneficiary, jan_30), Ok(500));
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.end_time, feb_29);
+        }
+
+        /// The per-year loop `days_to_year` replaced, kept as a reference
+        fn days_to_year_by_loop(days_since_epoch: u64) -> (u32, u64) {
+            let mut year = 1970u32;
+            let mut remaining_days = days_since_epoch;
+            loop {
+                let days_in_year = if VestingScheduler::is_leap_year(year) {
+                    366
+                } else {
+                    365
+                };
+                if remaining_days < days_in_year {
+                    break;
+                }
+                remaining_days -= days_in_year;
+                year += 1;
+            }
+            (year, remaining_days)
+        }
+
+        #[ink::test]
+        fn test_days_to_year_matches_loop() {
+            // Epoch, leap days, century years (2100 not leap, 2000/2400 leap), year ends
+            for days in [
+                0u64, 364, 365, 789, 10_956, 11_016, 11_322, 19_782, 47_481, 47_846, 157_372,
+                157_431, 157_737, 157_738, 200_000,
+            ] {
+                assert_eq!(
+                    VestingScheduler::days_to_year(days),
+                    days_to_year_by_loop(days),
+                    "day {days}"
+                );
+            }
+            // Every 97th day across a full 400-year span around year 2400 (97 is prime, so the
+            // samples drift through every position within a year)
+            for days in (146_000u64..146_097 * 2).step_by(97) {
+                assert_eq!(
+                    VestingScheduler::days_to_year(days),
+                    days_to_year_by_loop(days)
+                );
+            }
+        }
+
+        #[ink::test]
+        fn test_timestamp_to_datetime_year_2400() {
+            let contract = VestingScheduler::new();
+            // 2400-02-29 12:00:00 and 2400-12-31 23:59:59.999
+            let dt = contract.timestamp_to_datetime(13_574_606_400_000);
+            assert_eq!((dt.year, dt.month, dt.day, dt.hour), (2400, 2, 29, 12));
+            let dt = contract.timestamp_to_datetime(13_601_087_999_999);
+            assert_eq!((dt.year, dt.month, dt.day), (2400, 12, 31));
+            assert_eq!(
+                (dt.hour, dt.minute, dt.second, dt.millisecond),
+                (23, 59, 59, 999)
+            );
+        }
+
+        #[ink::test]
+        fn test_claim_vested_exact() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([102u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 40 * MS_PER_DAY,
+            );
+
+            // A stale quote (taken at day 30) is rejected, above or below
+            assert_eq!(
+                contract.claim_vested_exact(300_000),
+                Err(Error::SlippageExceeded)
+            );
+            assert_eq!(
+                contract.claim_vested_exact(500_000),
+                Err(Error::SlippageExceeded)
+            );
+            assert_eq!(
+                contract
+                    .get_vesting_schedule(beneficiary)
+                    .unwrap()
+                    .claimed_amount,
+                0
+            );
+
+            // The current quote goes through
+            assert_eq!(contract.claim_vested_exact(400_000), Ok(400_000));
+        }
+
+        #[ink::test]
+        fn test_is_fully_vested() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([103u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.is_fully_vested(beneficiary), None);
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, beneficiary, 1_000_000, start, end).unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end - 1);
+            assert_eq!(contract.is_fully_vested(beneficiary), Some(false));
+
+            // Fully vested at the end even though nothing was claimed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            assert_eq!(contract.is_fully_vested(beneficiary), Some(true));
+            assert_eq!(
+                contract
+                    .get_vesting_schedule(beneficiary)
+                    .unwrap()
+                    .claimed_amount,
+                0
+            );
+        }
+
+        #[ink::test]
+        fn test_amount_bucket_boundaries() {
+            assert_eq!(VestingScheduler::amount_bucket(0), 0);
+            assert_eq!(VestingScheduler::amount_bucket(999), 0);
+            assert_eq!(VestingScheduler::amount_bucket(1_000), 1);
+            assert_eq!(VestingScheduler::amount_bucket(999_999), 1);
+            assert_eq!(VestingScheduler::amount_bucket(1_000_000), 2);
+            // 10^18 (one whole token at 18 decimals)
+            assert_eq!(VestingScheduler::amount_bucket(10u128.pow(18)), 6);
+            assert_eq!(VestingScheduler::amount_bucket(Balance::MAX), 12);
+        }
+
+        #[ink::test]
+        fn test_suspend_and_resume_vesting() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([104u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, beneficiary, 1_000_000, start, end).unwrap();
+
+            // Suspend at day 30
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 30 * MS_PER_DAY,
+            );
+            assert_eq!(
+                contract.resume_vesting(beneficiary),
+                Err(Error::NotSuspended)
+            );
+            assert_eq!(contract.suspend_vesting(beneficiary), Ok(()));
+            assert_eq!(
+                contract.suspend_vesting(beneficiary),
+                Err(Error::ScheduleSuspended)
+            );
+            assert_eq!(
+                contract.extend_vesting(beneficiary, end + MS_PER_DAY),
+                Err(Error::ScheduleSuspended)
+            );
+
+            // Accrual is frozen at day 30, but what vested stays claimable
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 50 * MS_PER_DAY,
+            );
+            assert_eq!(contract.next_unlock_time(beneficiary), None);
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.claim_vested(), Ok(300_000));
+            assert_eq!(contract.claim_vested(), Err(Error::NoTokensAvailable));
+
+            // Resume after 20 days: the schedule shifts by 20 days and carries on
+            ink::env::test::set_caller(owner);
+            assert_eq!(contract.resume_vesting(beneficiary), Ok(()));
+            let event: VestingResumed = last_event();
+            assert_eq!(event.paused_ms, 20 * MS_PER_DAY);
+            assert_eq!(event.end_time, end + 20 * MS_PER_DAY);
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, start + 50 * MS_PER_DAY),
+                Ok(0)
+            );
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, start + 60 * MS_PER_DAY),
+                Ok(100_000)
+            );
+            // The original total still vests in full, just 20 days later
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, end + 20 * MS_PER_DAY),
+                Ok(700_000)
+            );
+        }
+
+        #[ink::test]
+        fn test_suspend_vesting_before_start_and_revoke() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([105u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start - 10 * MS_PER_DAY,
+            );
+            create_schedule(&mut contract, beneficiary, 1_000_000, start, end).unwrap();
+
+            // Suspended from before the start until day 10: only 10 days are lost
+            assert_eq!(contract.suspend_vesting(beneficiary), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 10 * MS_PER_DAY,
+            );
+            assert_eq!(contract.resume_vesting(beneficiary), Ok(()));
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.start_time, start + 10 * MS_PER_DAY);
+            assert_eq!(schedule.end_time, end + 10 * MS_PER_DAY);
+
+            // Revoking a suspended schedule keeps only what vested before the pause
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 30 * MS_PER_DAY,
+            );
+            assert_eq!(contract.suspend_vesting(beneficiary), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 60 * MS_PER_DAY,
+            );
+            assert_eq!(contract.revoke_vesting(beneficiary), Ok(()));
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.total_amount, 200_000);
+            assert!(schedule.suspended_at.is_none());
+            assert_eq!(
+                contract.suspend_vesting(beneficiary),
+                Err(Error::AlreadyRevoked)
+            );
+        }
+
+        #[ink::test]
+        fn test_reconcile() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([106u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            // Underfunded
+            ink::env::test::set_contract_balance(contract_address(), U256::from(600_000u128));
+            assert_eq!(contract.reconcile(), (600_000, 1_000_000, false));
+
+            // Claims only move tokens within the ledger: the contract still holds them
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 50 * MS_PER_DAY,
+            );
+            contract.claim_vested().unwrap();
+            assert_eq!(contract.reconcile(), (600_000, 1_000_000, false));
+
+            // Revoked tokens remain owed until withdrawn
+            ink::env::test::set_contract_balance(contract_address(), U256::from(1_000_000u128));
+            ink::env::test::set_caller(owner);
+            contract.revoke_vesting(beneficiary).unwrap();
+            assert_eq!(contract.reconcile(), (1_000_000, 1_000_000, true));
+            contract.withdraw_reclaimed().unwrap();
+            assert_eq!(contract.reconcile(), (500_000, 500_000, true));
+        }
+
+        #[ink::test]
+        fn test_create_vesting_schedule_with_id_is_idempotent() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([107u8; 20]);
+            let other: H160 = H160::from([108u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+
+            // A failed creation doesn't consume the id
+            assert_eq!(
+                contract.create_vesting_schedule_with_id(7, beneficiary, 0, start, end),
+                Err(Error::InvalidAmount)
+            );
+            assert_eq!(contract.get_schedule_id(7), None);
+
+            assert_eq!(
+                contract.create_vesting_schedule_with_id(7, beneficiary, 1_000, start, end),
+                Ok(())
+            );
+            assert_eq!(contract.get_schedule_id(7), Some(beneficiary));
+
+            // A retry with the same id is rejected, whatever its arguments
+            assert_eq!(
+                contract.create_vesting_schedule_with_id(7, beneficiary, 1_000, start, end),
+                Err(Error::IdAlreadyExists)
+            );
+            assert_eq!(
+                contract.create_vesting_schedule_with_id(7, other, 1_000, start, end),
+                Err(Error::IdAlreadyExists)
+            );
+            assert!(!contract.has_schedule(other));
+        }
+
+        #[ink::test]
+        fn test_claim_event_amount_readable() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([109u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new_with_config(TimestampUnit::Millis, 6, false);
+            contract
+                .set_event_verbosity(EventVerbosity::Verbose)
+                .unwrap();
+
+            // 1000 tokens at 6 decimals
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 15 * MS_PER_DAY,
+            );
+            contract.claim_vested().unwrap();
+
+            let event: TokensClaimedReadable = last_event();
+            assert_eq!(event.amount, 150_000_000);
+            assert_eq!(&event.amount_readable[..10], b"150.000000");
+            assert!(event.amount_readable[10..].iter().all(|b| *b == 0));
+        }
+
+        #[ink::test]
+        fn test_per_schedule_rounding() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let floor: H160 = H160::from([110u8; 20]);
+            let ceil: H160 = H160::from([111u8; 20]);
+            let default: H160 = H160::from([112u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // 10 tokens over 3 seconds: 3.33 tokens vest per second
+            let start = 1729512000000u64;
+            let end = start + 3000;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            for (beneficiary, mode) in [(floor, RoundingMode::Floor), (ceil, RoundingMode::Ceil)] {
+                assert_eq!(
+                    contract.create_vesting_schedule(
+                        beneficiary,
+                        10,
+                        start,
+                        end,
+                        ScheduleParams {
+                            rounding: Some(mode),
+                            ..Default::default()
+                        },
+                    ),
+                    Ok(())
+                );
+            }
+            create_schedule(&mut contract, default, 10, start, end).unwrap();
+
+            let at = start + 1000;
+            assert_eq!(contract.simulate_claimable_at(floor, at), Ok(3));
+            assert_eq!(contract.simulate_claimable_at(ceil, at), Ok(4));
+            assert_eq!(contract.simulate_claimable_at(default, at), Ok(3));
+
+            // The contract-wide mode only moves schedules without their own
+            assert_eq!(contract.set_rounding_mode(RoundingMode::Ceil), Ok(()));
+            assert_eq!(contract.simulate_claimable_at(floor, at), Ok(3));
+            assert_eq!(contract.simulate_claimable_at(default, at), Ok(4));
+        }
+
+        #[ink::test]
+        fn test_create_with_combined_params() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([179u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // TGE unlock, stepped curve and per-grant rounding on one grant
+            let start = 1729512000000u64;
+            let params = ScheduleParams {
+                initial_unlock: 100,
+                kind: VestingKind::Stepped { intervals: 3 },
+                rounding: Some(RoundingMode::Ceil),
+                ..Default::default()
+            };
+            assert_eq!(
+                contract.create_vesting_schedule(beneficiary, 1_001, start, start + 3000, params),
+                Ok(())
+            );
+
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.initial_unlock, 100);
+            assert_eq!(schedule.kind, VestingKind::Stepped { intervals: 3 });
+            assert_eq!(schedule.rounding, Some(RoundingMode::Ceil));
+            // Unlock plus one tranche of 901 / 3, rounded up
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, start + 1000),
+                Ok(401)
+            );
+        }
+
+        #[ink::test]
+        fn test_validate_vesting_params() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([113u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            contract.set_min_duration(MS_PER_DAY).unwrap();
+            contract.set_max_duration(365 * MS_PER_DAY).unwrap();
+            contract.set_max_backdate(30 * MS_PER_DAY).unwrap();
+
+            let validate = |b: H160, total: Balance, s: u64, e: u64| {
+                contract.validate_vesting_params(b, total, s, e)
+            };
+            assert_eq!(validate(beneficiary, 1_000, start, end), Ok(()));
+            assert_eq!(
+                validate(H160::zero(), 1_000, start, end),
+                Err(Error::InvalidBeneficiary)
+            );
+            assert_eq!(
+                validate(beneficiary, 0, start, end),
+                Err(Error::InvalidAmount)
+            );
+            assert_eq!(
+                validate(beneficiary, 1_000, end, start),
+                Err(Error::InvalidTimeRange)
+            );
+            assert_eq!(
+                validate(beneficiary, 1_000, start, start + 1000),
+                Err(Error::DurationTooShort)
+            );
+            assert_eq!(
+                validate(beneficiary, 1_000, start, start + 400 * MS_PER_DAY),
+                Err(Error::DurationTooLong)
+            );
+            assert_eq!(
+                validate(beneficiary, 1_000, start - 31 * MS_PER_DAY, end),
+                Err(Error::StartTimeTooFarInPast)
+            );
+
+            // Nothing was stored; callers don't need to be the owner
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(
+                contract.validate_vesting_params(beneficiary, 1_000, start, end),
+                Ok(())
+            );
+            assert!(!contract.has_schedule(beneficiary));
+        }
+
+        #[ink::test]
+        fn test_total_claimed_by_spans_grants() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([115u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.total_claimed_by(beneficiary), 0);
+
+            // First grant, 30% claimed
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 30 * MS_PER_DAY,
+            );
+            contract.claim_vested().unwrap();
+
+            // Revoked and pruned, then a second grant, 10% of it claimed
+            ink::env::test::set_caller(owner);
+            contract.revoke_vesting(beneficiary).unwrap();
+            contract.prune_schedule(beneficiary).unwrap();
+            let restart = start + 30 * MS_PER_DAY;
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                500_000,
+                restart,
+                restart + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                restart + 10 * MS_PER_DAY,
+            );
+            contract.claim_vested().unwrap();
+
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.claimed_amount, 50_000);
+            assert_eq!(contract.total_claimed_by(beneficiary), 350_000);
+        }
+
+        #[ink::test]
+        fn test_claim_with_zero_block_timestamp() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([116u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            // Genesis-like clock
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(0);
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.claim_vested(), Err(Error::TimeNotAvailable));
+            assert_eq!(
+                contract.claim_vested_to(owner),
+                Err(Error::TimeNotAvailable)
+            );
+
+            // Claims work again once the clock is set
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 10 * MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested(), Ok(100_000));
+        }
+
+        #[ink::test]
+        fn test_reassign_beneficiary() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let from: H160 = H160::from([117u8; 20]);
+            let estate: H160 = H160::from([118u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                from,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            ink::env::test::set_caller(from);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 20 * MS_PER_DAY,
+            );
+            contract.claim_vested().unwrap();
+
+            // Only the owner can redirect a grant, and never to the zero address
+            assert_eq!(
+                contract.reassign_beneficiary(from, estate),
+                Err(Error::Unauthorized)
+            );
+            ink::env::test::set_caller(owner);
+            assert_eq!(
+                contract.reassign_beneficiary(from, H160::zero()),
+                Err(Error::InvalidBeneficiary)
+            );
+
+            assert_eq!(contract.reassign_beneficiary(from, estate), Ok(()));
+            let event: BeneficiaryReassigned = last_event();
+            assert_eq!((event.from, event.to), (from, estate));
+            assert!(!contract.has_schedule(from));
+            let schedule = contract.get_vesting_schedule(estate).unwrap();
+            assert_eq!(schedule.claimed_amount, 200_000);
+            assert_eq!(contract.get_claim_history(estate).len(), 1);
+            assert_eq!(contract.get_claim_count(estate), 1);
+            assert_eq!(contract.get_all_beneficiaries(), vec![estate]);
+
+            // The estate keeps claiming where the grant left off
+            ink::env::test::set_caller(estate);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 30 * MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested(), Ok(100_000));
+        }
+
+        #[ink::test]
+        fn test_reassign_beneficiary_rejects_collision() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let from: H160 = H160::from([119u8; 20]);
+            let to: H160 = H160::from([120u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            assert_eq!(
+                contract.reassign_beneficiary(from, to),
+                Err(Error::NoVestingSchedule)
+            );
+            create_schedule(&mut contract, from, 1_000_000, start, end).unwrap();
+            create_schedule(&mut contract, to, 500_000, start, end).unwrap();
+
+            assert_eq!(
+                contract.reassign_beneficiary(from, to),
+                Err(Error::ScheduleAlreadyExists)
+            );
+            // Both grants are untouched
+            assert_eq!(
+                contract.get_vesting_schedule(from).unwrap().total_amount,
+                1_000_000
+            );
+            assert_eq!(
+                contract.get_vesting_schedule(to).unwrap().total_amount,
+                500_000
+            );
+        }
+
+        #[ink::test]
+        fn test_reassign_beneficiary_rejects_irrevocable() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let from = H160::from([183u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                from,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            contract.make_irrevocable(from).unwrap();
+
+            // The owner can't redirect the grant, e.g. to themselves
+            assert_eq!(
+                contract.reassign_beneficiary(from, owner),
+                Err(Error::NotRevocable)
+            );
+            assert!(contract.has_schedule(from));
+            assert!(!contract.has_schedule(owner));
+        }
+
+        #[ink::test]
+        fn test_new_with_schedule() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([121u8; 20]);
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_caller(owner);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            let contract = VestingScheduler::new_with_schedule(beneficiary, 1_000_000, start, end);
+
+            assert_eq!(contract.get_owner(), owner);
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.total_amount, 1_000_000);
+            assert_eq!((schedule.start_time, schedule.end_time), (start, end));
+            assert_eq!(contract.get_all_beneficiaries(), vec![beneficiary]);
+            assert_eq!(contract.total_outstanding(), 1_000_000);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "invalid initial schedule: InvalidTimeRange")]
+        fn test_new_with_schedule_traps_on_invalid_range() {
+            let start = 1729512000000u64;
+            VestingScheduler::new_with_schedule(H160::from([122u8; 20]), 1_000, start, start);
+        }
+
+        #[ink::test]
+        fn test_schedule_time_changed_event() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([123u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, beneficiary, 1_000_000, start, end).unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 10 * MS_PER_DAY,
+            );
+            let extended = end + 50 * MS_PER_DAY;
+            assert_eq!(contract.extend_vesting(beneficiary, extended), Ok(()));
+            let event: ScheduleTimeChanged = last_event();
+            assert_eq!(event.beneficiary, beneficiary);
+            assert_eq!((event.old_end_time, event.new_end_time), (end, extended));
+            assert_eq!(event.reason, TimeChangeReason::Extended as u8);
+
+            // Revocation ends the schedule now; its own event follows
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 20 * MS_PER_DAY,
+            );
+            assert_eq!(contract.revoke_vesting(beneficiary), Ok(()));
+            let event: ScheduleTimeChanged = nth_last_event(1);
+            assert_eq!(
+                (event.old_end_time, event.new_end_time),
+                (extended, start + 20 * MS_PER_DAY)
+            );
+            assert_eq!(event.reason, TimeChangeReason::Revoked as u8);
+        }
+
+        #[ink::test]
+        fn test_verify_invariants() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([124u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert!(!contract.verify_invariants(beneficiary));
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            assert!(contract.verify_invariants(beneficiary));
+
+            // Partially claimed
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 40 * MS_PER_DAY,
+            );
+            contract.claim_vested().unwrap();
+            assert!(contract.verify_invariants(beneficiary));
+
+            // A corrupted schedule is flagged
+            let mut schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            schedule.claimed_amount = schedule.total_amount + 1;
+            assert!(!VestingScheduler::invariants_hold(&schedule));
+        }
+
+        #[ink::test]
+        fn test_prune_grace_period() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([125u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.set_prune_grace(30 * MS_PER_DAY), Ok(()));
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, beneficiary, 1_000_000, start, end).unwrap();
+
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            contract.claim_vested().unwrap();
+
+            // Fully claimed, but still inside the grace window
+            ink::env::test::set_caller(owner);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                end + 30 * MS_PER_DAY - 1,
+            );
+            assert_eq!(
+                contract.prune_schedule(beneficiary),
+                Err(Error::PruneTooSoon)
+            );
+            assert!(contract.has_schedule(beneficiary));
+
+            // Outside the window
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                end + 30 * MS_PER_DAY,
+            );
+            assert_eq!(contract.prune_schedule(beneficiary), Ok(()));
+            assert!(!contract.has_schedule(beneficiary));
+        }
+
+        #[ink::test]
+        fn test_time_for_amount() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let linear: H160 = H160::from([126u8; 20]);
+            let stepped: H160 = H160::from([127u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.time_for_amount(linear, 1), None);
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, linear, 1_000_000, start, end).unwrap();
+            contract
+                .create_vesting_schedule_with_kind(
+                    stepped,
+                    1_000_000,
+                    start,
+                    end,
+                    VestingKind::Stepped { intervals: 4 },
+                )
+                .unwrap();
+
+            // 25% and 50% of a linear grant
+            assert_eq!(
+                contract.time_for_amount(linear, 250_000),
+                Some(start + 25 * MS_PER_DAY)
+            );
+            assert_eq!(
+                contract.time_for_amount(linear, 500_000),
+                Some(start + 50 * MS_PER_DAY)
+            );
+            assert_eq!(contract.time_for_amount(linear, 1_000_000), Some(end));
+            assert_eq!(contract.time_for_amount(linear, 1_000_001), None);
+
+            // Stepped: the first tranche boundary at or above the target
+            assert_eq!(
+                contract.time_for_amount(stepped, 250_000),
+                Some(start + 25 * MS_PER_DAY)
+            );
+            assert_eq!(
+                contract.time_for_amount(stepped, 300_000),
+                Some(start + 50 * MS_PER_DAY)
+            );
+        }
+
+        #[ink::test]
+        fn test_allowed_recipients() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([128u8; 20]);
+            let kyc_wallet: H160 = H160::from([129u8; 20]);
+            let unknown: H160 = H160::from([130u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            // Unrestricted until the owner whitelists someone
+            assert!(contract.is_recipient_allowed(beneficiary, unknown));
+            assert_eq!(
+                contract.add_allowed_recipient(beneficiary, kyc_wallet),
+                Ok(())
+            );
+            assert!(!contract.is_recipient_allowed(beneficiary, unknown));
+
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 10 * MS_PER_DAY,
+            );
+            assert_eq!(
+                contract.claim_vested_to(unknown),
+                Err(Error::RecipientNotAllowed)
+            );
+            assert_eq!(contract.claim_vested_to(kyc_wallet), Ok(100_000));
+            // The beneficiary itself is always allowed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 20 * MS_PER_DAY,
+            );
+            assert_eq!(contract.claim_vested_to(beneficiary), Ok(100_000));
+        }
+
+        #[ink::test]
+        fn test_remove_allowed_recipient_keeps_restriction() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([131u8; 20]);
+            let wallet: H160 = H160::from([132u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            contract.add_allowed_recipient(beneficiary, wallet).unwrap();
+            assert!(contract.is_recipient_allowed(beneficiary, wallet));
+
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(
+                contract.remove_allowed_recipient(beneficiary, wallet),
+                Err(Error::Unauthorized)
+            );
+            ink::env::test::set_caller(owner);
+            assert_eq!(
+                contract.remove_allowed_recipient(beneficiary, wallet),
+                Ok(())
+            );
+            // An empty whitelist doesn't lift the restriction
+            assert!(!contract.is_recipient_allowed(beneficiary, wallet));
+            assert!(contract.is_recipient_allowed(beneficiary, beneficiary));
+        }
+
+        #[ink::test]
+        fn test_error_codes_are_stable() {
+            // Changing any of these breaks off-chain consumers
+            let expected = [
+                (Error::Unauthorized, 1),
+                (Error::InvalidTimeRange, 2),
+                (Error::NoVestingSchedule, 3),
+                (Error::VestingNotStarted, 4),
+                (Error::NoTokensAvailable, 5),
+                (Error::StartTimeTooFarInPast, 6),
+                (Error::Reentrancy, 7),
+                (Error::ScheduleAlreadyExists, 8),
+                (Error::UnauthorizedOperator, 9),
+                (Error::InvalidInitialUnlock, 10),
+                (Error::InvalidFee, 11),
+                (Error::Overflow, 12),
+                (Error::ClaimTooSoon, 13),
+                (Error::InvalidSignature, 14),
+                (Error::BadNonce, 15),
+                (Error::InvalidIntervals, 16),
+                (Error::DurationTooLong, 17),
+                (Error::DurationTooShort, 18),
+                (Error::ProposalNotFound, 19),
+                (Error::AlreadyApproved, 20),
+                (Error::NotRevocable, 21),
+                (Error::AlreadyRevoked, 22),
+                (Error::NotFullyClaimed, 23),
+                (Error::TransferFailed, 24),
+                (Error::InvalidBeneficiary, 25),
+                (Error::InvalidAmount, 26),
+                (Error::SlippageExceeded, 27),
+                (Error::ScheduleSuspended, 28),
+                (Error::NotSuspended, 29),
+                (Error::IdAlreadyExists, 30),
+                (Error::TimeNotAvailable, 31),
+                (Error::PruneTooSoon, 32),
+                (Error::RecipientNotAllowed, 33),
+                (Error::VestingAlreadyStarted, 34),
+                (Error::EndTimeInPast, 35),
+                (Error::InvalidDateTime, 36),
+                (Error::TooManyBeneficiaries, 37),
+                (Error::UnsupportedKind, 38),
+            ];
+            assert_eq!(expected.len(), Error::ALL.len());
+            for (error, code) in expected {
+                assert_eq!(error.code(), code, "{error:?}");
+                // The SCALE discriminant follows declaration order and must agree
+                assert_eq!(error as u16 + 1, code);
+            }
+
+            let contract = VestingScheduler::new();
+            let table = contract.error_code_table();
+            assert_eq!(table.len(), Error::ALL.len());
+            assert_eq!(table[0], (1, label(b"Unauthorized")));
+            assert_eq!(table[32], (33, label(b"RecipientNotAllowed")));
+        }
+
+        #[ink::test]
+        fn test_top_up_vesting() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([133u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, beneficiary, 1_000_000, start, end).unwrap();
+
+            // Halfway through, add 500k to vest over the remaining 50 days
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 50 * MS_PER_DAY,
+            );
+            assert_eq!(
+                contract.top_up_vesting(beneficiary, 0),
+                Err(Error::InvalidAmount)
+            );
+            assert_eq!(contract.top_up_vesting(beneficiary, 500_000), Ok(()));
+            let event: VestingToppedUp = last_event();
+            assert_eq!((event.additional, event.new_total), (500_000, 1_500_000));
+            assert_eq!(contract.total_outstanding(), 1_500_000);
+
+            // Nothing vests retroactively; the remaining 1M vests over 50 days
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, start + 50 * MS_PER_DAY),
+                Ok(500_000)
+            );
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, start + 75 * MS_PER_DAY),
+                Ok(1_000_000)
+            );
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, end),
+                Ok(1_500_000)
+            );
+            assert_eq!(
+                contract.get_vesting_schedule(beneficiary).unwrap().end_time,
+                end
+            );
+
+            // No window left once the schedule has ended
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            assert_eq!(
+                contract.top_up_vesting(beneficiary, 1),
+                Err(Error::InvalidTimeRange)
+            );
+        }
+
+        #[ink::test]
+        fn test_claim_exactly_at_start_time() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let plain: H160 = H160::from([134u8; 20]);
+            let with_unlock: H160 = H160::from([135u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start - 1);
+            create_schedule(&mut contract, plain, 1_000_000, start, end).unwrap();
+            contract
+                .create_vesting_schedule(
+                    with_unlock,
+                    1_000_000,
+                    start,
+                    end,
+                    ScheduleParams {
+                        initial_unlock: 100_000,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+
+            // Vesting has started but no time has elapsed yet
+            ink::env::test::set_caller(plain);
+            assert_eq!(contract.claim_vested(), Err(Error::NoTokensAvailable));
+
+            // The TGE unlock is claimable at exactly start_time
+            ink::env::test::set_caller(with_unlock);
+            assert_eq!(contract.claim_vested(), Ok(100_000));
+            assert_eq!(contract.claim_vested(), Err(Error::NoTokensAvailable));
+
+            // A day later the curve has begun for both
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start + MS_PER_DAY);
+            assert_eq!(contract.claim_vested(), Ok(9_000));
+            ink::env::test::set_caller(plain);
+            assert_eq!(contract.claim_vested(), Ok(10_000));
+        }
+
+        #[ink::test]
+        fn test_vesting_rate_per_ms() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([136u8; 20]);
+            let stepped: H160 = H160::from([137u8; 20]);
+            let fractional: H160 = H160::from([182u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.vesting_rate_per_ms(beneficiary), None);
+
+            // 864M tokens over 100 days: 100 tokens per ms
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, beneficiary, 864_000_000_000, start, end).unwrap();
+            assert_eq!(
+                contract.vesting_rate_per_ms(beneficiary),
+                Some(100 * RATE_SCALE)
+            );
+
+            // Fractional rates survive the fixed-point scaling
+            create_schedule(&mut contract, fractional, 1_000_000, start, end).unwrap();
+            assert_eq!(
+                contract.vesting_rate_per_ms(fractional),
+                Some(115_740_740_740_740)
+            );
+
+            contract
+                .create_vesting_schedule_with_kind(
+                    stepped,
+                    1_000_000,
+                    start,
+                    end,
+                    VestingKind::Stepped { intervals: 4 },
+                )
+                .unwrap();
+            assert_eq!(contract.vesting_rate_per_ms(stepped), None);
+        }
+
+        #[ink::test]
+        fn test_cancel_pending_vesting() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([138u8; 20]);
+            let other: H160 = H160::from([139u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start - MS_PER_DAY);
+            create_schedule(&mut contract, beneficiary, 1_000_000, start, end).unwrap();
+            create_schedule(&mut contract, other, 500_000, start, end).unwrap();
+            assert_eq!(contract.total_outstanding(), 1_500_000);
+
+            assert_eq!(contract.cancel_pending_vesting(beneficiary), Ok(()));
+            let event: VestingCancelled = last_event();
+            assert_eq!(event.total_amount, 1_000_000);
+            assert!(!contract.has_schedule(beneficiary));
+            assert_eq!(contract.get_all_beneficiaries(), vec![other]);
+            assert_eq!(contract.total_outstanding(), 500_000);
+            // The released allocation is reclaimable, even from `fund()` deposits
+            assert_eq!(contract.get_reclaimable(), 1_000_000);
+            ink::env::test::set_value_transferred(U256::from(1_500_000u128));
+            assert_eq!(contract.fund(), Ok(1_500_000));
+            ink::env::test::set_contract_balance(contract_address(), U256::from(1_500_000u128));
+            assert_eq!(contract.withdraw_reclaimed(), Ok(1_000_000));
+            assert_eq!(contract.reconcile(), (500_000, 500_000, true));
+        }
+
+        #[ink::test]
+        fn test_cancel_pending_vesting_after_start_fails() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([140u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start - MS_PER_DAY);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+            contract.make_irrevocable(beneficiary).unwrap();
+            assert_eq!(
+                contract.cancel_pending_vesting(beneficiary),
+                Err(Error::NotRevocable)
+            );
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            assert_eq!(
+                contract.cancel_pending_vesting(beneficiary),
+                Err(Error::VestingAlreadyStarted)
+            );
+            assert!(contract.has_schedule(beneficiary));
+        }
+
+        #[ink::test]
+        fn test_vested_at_historical_points() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([141u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(
+                contract.vested_at(beneficiary, 0),
+                Err(Error::NoVestingSchedule)
+            );
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, beneficiary, 1_000_000, start, end).unwrap();
+
+            // Claims don't change the gross figure
+            ink::env::test::set_caller(beneficiary);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 60 * MS_PER_DAY,
+            );
+            contract.claim_vested().unwrap();
+
+            assert_eq!(contract.vested_at(beneficiary, start - 1), Ok(0));
+            assert_eq!(contract.vested_at(beneficiary, start), Ok(0));
+            assert_eq!(
+                contract.vested_at(beneficiary, start + 10 * MS_PER_DAY),
+                Ok(100_000)
+            );
+            assert_eq!(
+                contract.vested_at(beneficiary, start + 45 * MS_PER_DAY),
+                Ok(450_000)
+            );
+            assert_eq!(
+                contract.vested_at(beneficiary, start + 60 * MS_PER_DAY),
+                Ok(600_000)
+            );
+            assert_eq!(contract.vested_at(beneficiary, end), Ok(1_000_000));
+            assert_eq!(
+                contract.simulate_claimable_at(beneficiary, start + 45 * MS_PER_DAY),
+                Ok(0)
+            );
+        }
+
+        #[ink::test]
+        fn test_end_time_in_past_rejected_unless_allowed() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary: H160 = H160::from([142u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(end);
+            assert_eq!(
+                create_schedule(&mut contract, beneficiary, 1_000_000, start, end),
+                Err(Error::EndTimeInPast)
+            );
+            assert_eq!(
+                contract.validate_vesting_params(beneficiary, 1_000_000, start, end),
+                Err(Error::EndTimeInPast)
+            );
+
+            // Backfills are possible once the owner opts in
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.set_allow_past_end(true), Err(Error::Unauthorized));
+            ink::env::test::set_caller(owner);
+            assert_eq!(contract.set_allow_past_end(true), Ok(()));
+            assert_eq!(
+                create_schedule(&mut contract, beneficiary, 1_000_000, start, end),
+                Ok(())
+            );
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.claim_vested(), Ok(1_000_000));
+        }
+
+        #[ink::test]
+        fn test_set_fee_config() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let treasury = H160::from([143u8; 20]);
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            assert_eq!(contract.set_fee_config(treasury, 250), Ok(()));
+            assert_eq!(contract.get_fee_recipient(), treasury);
+            assert_eq!(contract.get_claim_fee_bps(), 250);
+            let event = last_event::<FeeConfigUpdated>();
+            assert_eq!(event.recipient, treasury);
+            assert_eq!(event.bps, 250);
+
+            // Invalid configs leave the previous one in place
+            assert_eq!(
+                contract.set_fee_config(treasury, 10_001),
+                Err(Error::InvalidFee)
+            );
+            assert_eq!(
+                contract.set_fee_config(H160::zero(), 100),
+                Err(Error::InvalidBeneficiary)
+            );
+            assert_eq!(
+                contract.set_fee_recipient(H160::zero()),
+                Err(Error::InvalidBeneficiary)
+            );
+            assert_eq!(contract.get_fee_recipient(), treasury);
+            assert_eq!(contract.get_claim_fee_bps(), 250);
+
+            ink::env::test::set_caller(treasury);
+            assert_eq!(
+                contract.set_fee_config(treasury, 0),
+                Err(Error::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn test_create_vesting_from_rate() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([144u8; 20]);
+            let start = 1729512000000u64;
+            let month = 30 * MS_PER_DAY;
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+
+            // 500 tokens a month for 24 months
+            assert_eq!(
+                contract.create_vesting_from_rate(beneficiary, 500, 24, start, month),
+                Ok(())
+            );
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.total_amount, 12_000);
+            assert_eq!(schedule.end_time, start + 24 * month);
+            assert_eq!(schedule.kind, VestingKind::Stepped { intervals: 24 });
+
+            // One period's worth unlocks at each boundary
+            assert_eq!(contract.vested_at(beneficiary, start + month - 1), Ok(0));
+            assert_eq!(
+                contract.vested_at(beneficiary, start + 3 * month),
+                Ok(1_500)
+            );
+
+            let other = H160::from([145u8; 20]);
+            assert_eq!(
+                contract.create_vesting_from_rate(other, u128::MAX, 2, start, month),
+                Err(Error::Overflow)
+            );
+            assert_eq!(
+                contract.create_vesting_from_rate(other, 500, 2, start, u64::MAX),
+                Err(Error::Overflow)
+            );
+            assert_eq!(
+                contract.create_vesting_from_rate(other, 500, 0, start, month),
+                Err(Error::InvalidIntervals)
+            );
+        }
+
+        #[ink::test]
+        fn test_export_schedule_round_trip() {
+            use ink::scale::Decode;
+
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([146u8; 20]);
+            let start = 1729512000000u64;
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000_000,
+                start,
+                start + 100 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            let bytes = contract.export_schedule(beneficiary).unwrap();
+            let (version, exported_beneficiary, schedule) =
+                <(u8, H160, VestingSchedule)>::decode(&mut &bytes[..]).unwrap();
+            assert_eq!(version, EXPORT_FORMAT_VERSION);
+            assert_eq!(exported_beneficiary, beneficiary);
+            let stored = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.total_amount, stored.total_amount);
+            assert_eq!(schedule.start_time, stored.start_time);
+            assert_eq!(schedule.end_time, stored.end_time);
+            assert_eq!(schedule.kind, stored.kind);
+
+            assert_eq!(contract.export_schedule(H160::from([147u8; 20])), None);
+        }
+
+        #[ink::test]
+        fn test_get_schedules_readable_batch() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let first = H160::from([148u8; 20]);
+            let missing = H160::from([149u8; 20]);
+            let second = H160::from([150u8; 20]);
+            let start = 1729512000000u64; // 2024-10-21 12:00:00
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            create_schedule(&mut contract, first, 1_000, start, start + MS_PER_DAY).unwrap();
+            create_schedule(&mut contract, second, 2_000, start, start + 2 * MS_PER_DAY).unwrap();
+
+            let batch = contract.get_schedules_readable_batch(vec![first, missing, second]);
+            assert_eq!(batch.len(), 3);
+            let (schedule, start_str, end_str) = batch[0].as_ref().unwrap();
+            assert_eq!(schedule.total_amount, 1_000);
+            assert_eq!(start_str, b"2024-10-21 12:00:00");
+            assert_eq!(end_str, b"2024-10-22 12:00:00");
+            assert!(batch[1].is_none());
+            let (schedule, _, end_str) = batch[2].as_ref().unwrap();
+            assert_eq!(schedule.total_amount, 2_000);
+            assert_eq!(end_str, b"2024-10-23 12:00:00");
+
+            // Oversized requests are truncated
+            let many = vec![first; MAX_BATCH_SIZE as usize + 1];
+            assert_eq!(
+                contract.get_schedules_readable_batch(many).len(),
+                MAX_BATCH_SIZE as usize
+            );
+        }
+
+        #[ink::test]
+        fn test_datetime_to_timestamp_rejects_invalid_fields() {
+            let contract = VestingScheduler::new();
+            let valid = || DateTime {
+                year: 2024,
+                month: 2,
+                day: 29,
+                hour: 23,
+                minute: 59,
+                second: 59,
+                millisecond: 999,
+            };
+            assert!(contract.datetime_to_timestamp(valid()).is_ok());
+
+            let invalid = [
+                DateTime {
+                    month: 0,
+                    ..valid()
+                },
+                DateTime {
+                    month: 13,
+                    ..valid()
+                },
+                DateTime { day: 0, ..valid() },
+                DateTime { day: 30, ..valid() },
+                // 2023 is not a leap year
+                DateTime {
+                    year: 2023,
+                    ..valid()
+                },
+                DateTime {
+                    month: 4,
+                    day: 31,
+                    ..valid()
+                },
+                DateTime {
+                    hour: 24,
+                    ..valid()
+                },
+                DateTime {
+                    minute: 60,
+                    ..valid()
+                },
+                DateTime {
+                    second: 60,
+                    ..valid()
+                },
+                DateTime {
+                    millisecond: 1000,
+                    ..valid()
+                },
+            ];
+            for dt in invalid {
+                assert_eq!(
+                    contract.datetime_to_timestamp(dt),
+                    Err(Error::InvalidDateTime)
+                );
+            }
+
+            // Schedule creation from dates surfaces the error
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_caller(accounts.alice.into());
+            let mut contract = VestingScheduler::new();
+            assert_eq!(
+                contract.create_vesting_schedule_from_dates(
+                    H160::from([151u8; 20]),
+                    1_000,
+                    DateTime {
+                        month: 2,
+                        day: 30,
+                        ..valid()
+                    },
+                    DateTime {
+                        year: 2025,
+                        ..valid()
+                    },
+                ),
+                Err(Error::InvalidDateTime)
+            );
+        }
+
+        #[ink::test]
+        fn test_claim_vested_with_memo() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([152u8; 20]);
+            let start = 1729512000000u64;
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000,
+                start,
+                start + 10 * MS_PER_DAY,
+            )
+            .unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 5 * MS_PER_DAY,
+            );
+            ink::env::test::set_caller(beneficiary);
+            let memo = label(b"PAYROLL-2024-10");
+            assert_eq!(contract.claim_vested_with_memo(memo), Ok(500));
+
+            let event = last_event::<TokensClaimedWithMemo>();
+            assert_eq!(event.beneficiary, beneficiary);
+            assert_eq!(event.memo, memo);
+            assert_eq!(event.amount, 500);
+            // The regular claim event is still emitted first
+            assert_eq!(nth_last_event::<TokensClaimed>(1).amount, 500);
+        }
+
+        #[ink::test]
+        fn test_min_vest_per_day_floor() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([153u8; 20]);
+            let start = 1729512000000u64;
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            // Back-loaded 10_000 over 100 days with at least 50 a day
+            assert_eq!(
+                contract.create_vesting_schedule(
+                    beneficiary,
+                    10_000,
+                    start,
+                    start + 100 * MS_PER_DAY,
+                    ScheduleParams {
+                        kind: VestingKind::Quadratic,
+                        min_vest_per_day: 50,
+                        ..Default::default()
+                    },
+                ),
+                Ok(())
+            );
+
+            // Early on the curve lags (10 days: 100) and the floor dominates
+            assert_eq!(
+                contract.vested_at(beneficiary, start + 10 * MS_PER_DAY),
+                Ok(500)
+            );
+            // Partial days don't count towards the floor
+            assert_eq!(
+                contract.vested_at(beneficiary, start + 10 * MS_PER_DAY + MS_PER_DAY / 2),
+                Ok(500)
+            );
+            // The curves cross at 50 days (2_500 each)
+            assert_eq!(
+                contract.vested_at(beneficiary, start + 50 * MS_PER_DAY),
+                Ok(2_500)
+            );
+            // Later the curve dominates (80 days: 6_400 vs 4_000)
+            assert_eq!(
+                contract.vested_at(beneficiary, start + 80 * MS_PER_DAY),
+                Ok(6_400)
+            );
+
+            // A floor above the average rate completes the grant early, capped at the total
+            let eager = H160::from([154u8; 20]);
+            assert_eq!(
+                contract.create_vesting_schedule(
+                    eager,
+                    1_000,
+                    start,
+                    start + 10 * MS_PER_DAY,
+                    ScheduleParams {
+                        min_vest_per_day: 200,
+                        ..Default::default()
+                    },
+                ),
+                Ok(())
+            );
+            assert_eq!(contract.vested_at(eager, start + 2 * MS_PER_DAY), Ok(400));
+            assert_eq!(contract.vested_at(eager, start + 6 * MS_PER_DAY), Ok(1_000));
+        }
+
+        #[ink::test]
+        fn test_get_beneficiaries_by_end_time() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let late = H160::from([155u8; 20]);
+            let early = H160::from([156u8; 20]);
+            let middle = H160::from([157u8; 20]);
+            let start = 1729512000000u64;
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.get_beneficiaries_by_end_time(), vec![]);
+
+            create_schedule(&mut contract, late, 1_000, start, start + 30 * MS_PER_DAY).unwrap();
+            create_schedule(&mut contract, early, 1_000, start, start + 10 * MS_PER_DAY).unwrap();
+            create_schedule(&mut contract, middle, 1_000, start, start + 20 * MS_PER_DAY).unwrap();
+
+            assert_eq!(
+                contract.get_beneficiaries_by_end_time(),
+                vec![
+                    (early, start + 10 * MS_PER_DAY),
+                    (middle, start + 20 * MS_PER_DAY),
+                    (late, start + 30 * MS_PER_DAY),
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn test_max_beneficiaries_cap() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let first = H160::from([158u8; 20]);
+            let second = H160::from([159u8; 20]);
+            let third = H160::from([160u8; 20]);
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            assert_eq!(contract.get_max_beneficiaries(), 0);
+            assert_eq!(contract.set_max_beneficiaries(2), Ok(()));
+
+            create_schedule(&mut contract, first, 1_000, start, end).unwrap();
+            create_schedule(&mut contract, second, 1_000, start, end).unwrap();
+            assert_eq!(
+                create_schedule(&mut contract, third, 1_000, start, end),
+                Err(Error::TooManyBeneficiaries)
+            );
+            assert_eq!(contract.get_all_beneficiaries(), vec![first, second]);
+
+            // An existing holder can't be given a second grant
+            assert_eq!(
+                create_schedule(&mut contract, first, 2_000, start, end),
+                Err(Error::ScheduleAlreadyExists)
+            );
+
+            // Freeing a slot, or lifting the cap, allows new grants again
+            assert_eq!(contract.cancel_pending_vesting(second), Ok(()));
+            assert_eq!(
+                create_schedule(&mut contract, third, 1_000, start, end),
+                Ok(())
+            );
+            assert_eq!(contract.set_max_beneficiaries(0), Ok(()));
+            assert_eq!(
+                create_schedule(&mut contract, second, 1_000, start, end),
+                Ok(())
+            );
+
+            ink::env::test::set_caller(first);
+            assert_eq!(contract.set_max_beneficiaries(5), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn test_resync_counters() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let first = H160::from([163u8; 20]);
+            let second = H160::from([164u8; 20]);
+            let start = 1729512000000u64;
+
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            create_schedule(&mut contract, first, 1_000, start, start + 10 * MS_PER_DAY).unwrap();
+            create_schedule(&mut contract, second, 3_000, start, start + 10 * MS_PER_DAY).unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 5 * MS_PER_DAY,
+            );
+            ink::env::test::set_caller(first);
+            assert_eq!(contract.claim_vested(), Ok(500));
+            assert_eq!(contract.total_outstanding(), 3_500);
 
-            // Keep subtracting full years until we have less than 365 days left
-            while remaining_days >= 365 {
-                let days_in_year = if Self::is_leap_year(year) { 366 } else { 365 };
-                remaining_days -= days_in_year;
-                year += 1;
-            }
+            // Simulate drift from a faulty migration
+            contract.total_vested_allocated = 9_999;
+            contract.total_claimed = 7;
+            assert_eq!(contract.total_outstanding(), 9_992);
 
-            // Calculate month and day
-            let (month, day) = Self::days_to_month_day(remaining_days as u32, year);
+            ink::env::test::set_caller(first);
+            assert_eq!(contract.resync_counters(), Err(Error::Unauthorized));
 
-            DateTime {
-                year,
-                month,
-                day,
-                hour,
-                minute,
-                second,
-            }
-        }
-        /// Check if a year is a leap year
-        fn is_leap_year(year: u32) -> bool {
-            (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+            ink::env::test::set_caller(owner);
+            assert_eq!(contract.resync_counters(), Ok(()));
+            assert_eq!(contract.total_vested_allocated, 4_000);
+            assert_eq!(contract.total_claimed, 500);
+            assert_eq!(contract.total_outstanding(), 3_500);
+
+            let event = last_event::<CountersResynced>();
+            assert_eq!(event.old_total_allocated, 9_999);
+            assert_eq!(event.new_total_allocated, 4_000);
+            assert_eq!(event.old_total_claimed, 7);
+            assert_eq!(event.new_total_claimed, 500);
         }
 
-        /// Convert day of year to month and day
-        /// day_of_year is 0-indexed (0 = Jan 1st)
-        fn days_to_month_day(day_of_year: u32, year: u32) -> (u8, u8) {
-            let is_leap = Self::is_leap_year(year);
-            let days_in_months = if is_leap {
-                [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-            } else {
-                [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-            };
+        #[ink::test]
+        fn test_sweep_untracked_keeps_claimed_and_funded_tokens() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([165u8; 20]);
+            let start = 1729512000000u64;
 
-            let mut remaining = day_of_year;
-            for (i, &days) in days_in_months.iter().enumerate() {
-                if remaining < days {
-                    return ((i + 1) as u8, (remaining + 1) as u8);
-                }
-                remaining = remaining.saturating_sub(days);
-            }
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000,
+                start,
+                start + 10 * MS_PER_DAY,
+            )
+            .unwrap();
+            ink::env::test::set_contract_balance(contract_address(), U256::from(1_000u128));
 
-            // Fallback (shouldn't reach here with valid input)
-            (12, 31)
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 5 * MS_PER_DAY,
+            );
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.claim_vested(), Ok(500));
+
+            // The claimed tokens are still held for the beneficiary
+            ink::env::test::set_caller(owner);
+            assert_eq!(contract.sweep_untracked(), Err(Error::NoTokensAvailable));
+            assert_eq!(contract.reconcile(), (1_000, 1_000, true));
+
+            // Deposits through fund() beyond the grants are not swept either
+            ink::env::test::set_value_transferred(U256::from(1_500u128));
+            assert_eq!(contract.fund(), Ok(1_500));
+            ink::env::test::set_contract_balance(contract_address(), U256::from(1_600u128));
+            assert_eq!(contract.sweep_untracked(), Ok(100));
+            assert_eq!(contract.reconcile(), (1_500, 1_500, true));
         }
 
-        /// Format DateTime as a byte array: "YYYY-MM-DD HH:MM:SS"
-        /// Note: Returns fixed-size array for no_std compatibility
-        fn format_datetime(&self, dt: DateTime) -> [u8; 19] {
-            let mut result = [b'0'; 19];
+        #[ink::test]
+        fn test_vesting_progress_after_revoke_before_start() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([166u8; 20]);
+            let start = 1729512000000u64;
 
-            // Format: YYYY-MM-DD HH:MM:SS
-            // Year (4 digits)
-            Self::write_u32(&mut result[0..4], dt.year);
-            result[4] = b'-';
-            // Month (2 digits)
-            Self::write_u8(&mut result[5..7], dt.month);
-            result[7] = b'-';
-            // Day (2 digits)
-            Self::write_u8(&mut result[8..10], dt.day);
-            result[10] = b' ';
-            // Hour (2 digits)
-            Self::write_u8(&mut result[11..13], dt.hour);
-            result[13] = b':';
-            // Minute (2 digits)
-            Self::write_u8(&mut result[14..16], dt.minute);
-            result[16] = b':';
-            // Second (2 digits)
-            Self::write_u8(&mut result[17..19], dt.second);
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start - MS_PER_DAY);
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000,
+                start,
+                start + 10 * MS_PER_DAY,
+            )
+            .unwrap();
+            contract.revoke_vesting(beneficiary).unwrap();
 
-            result
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.end_time, schedule.start_time);
+            assert_eq!(contract.vesting_progress_bps(beneficiary), Some(MAX_BPS));
         }
 
-        /// Write a u32 value to a byte buffer as ASCII digits
-        fn write_u32(buf: &mut [u8], mut val: u32) {
-            for i in (0..buf.len()).rev() {
-                buf[i] = b'0' + (val % 10) as u8;
-                val /= 10;
-            }
-        }
+        #[ink::test]
+        fn test_next_unlock_time_after_revoke_before_start() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([167u8; 20]);
+            let start = 1729512000000u64;
 
-        /// Write a u8 value to a 2-byte buffer as ASCII digits
-        fn write_u8(buf: &mut [u8], val: u8) {
-            buf[0] = b'0' + (val / 10);
-            buf[1] = b'0' + (val % 10);
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start - MS_PER_DAY);
+            contract
+                .create_vesting_schedule_with_kind(
+                    beneficiary,
+                    1_000,
+                    start,
+                    start + 10 * MS_PER_DAY,
+                    VestingKind::Stepped { intervals: 4 },
+                )
+                .unwrap();
+            contract.revoke_vesting(beneficiary).unwrap();
+
+            assert_eq!(contract.next_unlock_time(beneficiary), None);
         }
 
-        // Helper functions
-        // Calculates the amount vested linearly
-        fn calculate_vested_amount(
-            &self,
-            schedule: &VestingSchedule,
-            current_time: u64,
-        ) -> Balance {
-            if current_time < schedule.start_time {
-                return 0;
+        #[ink::test]
+        fn test_datetime_to_timestamp_rejects_unsupported_years() {
+            let contract = VestingScheduler::new();
+            let new_year = |year| DateTime {
+                year,
+                month: 1,
+                day: 1,
+                hour: 0,
+                minute: 0,
+                second: 0,
+                millisecond: 0,
+            };
+            assert_eq!(contract.datetime_to_timestamp(new_year(1970)), Ok(0));
+            for year in [1969, 10_000, u32::MAX] {
+                assert_eq!(
+                    contract.datetime_to_timestamp(new_year(year)),
+                    Err(Error::InvalidDateTime)
+                );
             }
+            let june_1969 = DateTime {
+                month: 6,
+                ..new_year(1969)
+            };
+            assert_eq!(
+                contract.datetime_to_timestamp(june_1969),
+                Err(Error::InvalidDateTime)
+            );
 
-            if current_time >= schedule.end_time {
-                return schedule.total_amount;
+            // The closed form agrees with counting year by year
+            let mut days = 0u64;
+            for year in 1970..=9999u32 {
+                assert_eq!(VestingScheduler::days_before_year(year), days, "{year}");
+                days += if VestingScheduler::is_leap_year(year) {
+                    366
+                } else {
+                    365
+                };
             }
-
-            // Linear vesting calculation
-            let elapsed = current_time.saturating_sub(schedule.start_time);
-            let duration = schedule.end_time.saturating_sub(schedule.start_time);
-
-            // vested = (total * elapsed) / duration
-            let vested = (schedule.total_amount as u128)
-                .saturating_mul(elapsed as u128)
-                .saturating_div(duration as u128) as Balance;
-
-            vested
         }
-    }
-    #[cfg(test)]
-    mod tests {
-        use super::*;
 
         #[ink::test]
-        fn test_vesting_lifecycle() {
+        fn test_transfer_schedule_keeps_recipient_restriction() {
             let accounts = ink::env::test::default_accounts();
-            // Convert AccountId to H160
             let owner: H160 = accounts.alice.into();
-            let beneficiary: H160 = H160::from([1u8; 20]);
+            let beneficiary = H160::from([169u8; 20]);
+            let new_holder = H160::from([170u8; 20]);
+            let kyc_wallet = H160::from([171u8; 20]);
+            let other_wallet = H160::from([172u8; 20]);
+            let start = 1729512000000u64;
 
-            // Set caller to owner BEFORE creating contract
             ink::env::test::set_caller(owner);
             let mut contract = VestingScheduler::new();
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000,
+                start,
+                start + 10 * MS_PER_DAY,
+            )
+            .unwrap();
+            contract
+                .add_allowed_recipient(beneficiary, kyc_wallet)
+                .unwrap();
 
-            // Set initial block timestamp: Oct 21, 2024, 10:00:00 UTC
-            let start_time = 1729512000000u64;
-            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start_time);
-
-            // Create vesting schedule: 1M tokens over 100 days
-            let total_amount = 1_000_000;
-            let end_time = start_time + (100 * 24 * 60 * 60 * 1000); // 100 days later
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(contract.transfer_schedule(new_holder), Ok(()));
 
-            let result =
-                contract.create_vesting_schedule(beneficiary, total_amount, start_time, end_time);
-            assert!(
-                result.is_ok(),
-                "create_vesting_schedule failed: {:?}",
-                result
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 5 * MS_PER_DAY,
+            );
+            ink::env::test::set_caller(new_holder);
+            assert_eq!(
+                contract.claim_vested_to(other_wallet),
+                Err(Error::RecipientNotAllowed)
             );
+            // Whitelist entries don't carry over either
+            assert_eq!(
+                contract.claim_vested_to(kyc_wallet),
+                Err(Error::RecipientNotAllowed)
+            );
+            assert_eq!(contract.claim_vested_to(new_holder), Ok(500));
+        }
 
-            // Switch caller to beneficiary to claim
-            ink::env::test::set_caller(beneficiary);
+        #[ink::test]
+        fn test_transfer_schedule_to_zero_address_rejected() {
+            let accounts = ink::env::test::default_accounts();
+            let beneficiary = H160::from([173u8; 20]);
+            let start = 1729512000000u64;
 
-            // Advance time by 50 days
-            let fifty_days_later = start_time + (50 * 24 * 60 * 60 * 1000);
-            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(fifty_days_later);
+            ink::env::test::set_caller(accounts.alice.into());
+            let mut contract = VestingScheduler::new();
+            create_schedule(&mut contract, beneficiary, 1_000, start, start + MS_PER_DAY).unwrap();
 
-            // Should be able to claim 50% of tokens
-            let claimed = contract.claim_vested().unwrap();
-            assert_eq!(claimed, 500_000);
+            ink::env::test::set_caller(beneficiary);
+            assert_eq!(
+                contract.transfer_schedule(H160::zero()),
+                Err(Error::InvalidBeneficiary)
+            );
+            assert!(contract.has_schedule(beneficiary));
+        }
 
-            // Advance to after vesting ends
-            let after_end = end_time + 1000;
-            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(after_end);
+        #[ink::test]
+        fn test_export_private_schedule() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([174u8; 20]);
+            let stranger = H160::from([175u8; 20]);
+            let start = 1729512000000u64;
 
-            // Should be able to claim remaining 50%
-            let remaining = contract.claim_vested().unwrap();
-            assert_eq!(remaining, 500_000);
+            ink::env::test::set_caller(owner);
+            let mut contract =
+                VestingScheduler::new_with_config(TimestampUnit::Millis, DEFAULT_DECIMALS, true);
+            create_schedule(&mut contract, beneficiary, 1_000, start, start + MS_PER_DAY).unwrap();
+            assert!(contract.export_schedule(beneficiary).is_some());
 
-            // No more tokens to claim
-            let result = contract.claim_vested();
-            assert_eq!(result, Err(Error::NoTokensAvailable));
+            ink::env::test::set_caller(stranger);
+            assert_eq!(contract.export_schedule(beneficiary), None);
+
+            ink::env::test::set_caller(beneficiary);
+            assert!(contract.export_schedule(beneficiary).is_some());
         }
 
         #[ink::test]
-        fn test_timestamp_conversion() {
-            let contract = VestingScheduler::new();
+        fn test_progress_views_freeze_while_suspended() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([176u8; 20]);
+            let start = 1729512000000u64;
 
-            // Test known timestamp: Oct 21, 2024, 12:00:00 UTC
-            let timestamp = 1729512000000u64;
-            let dt = contract.timestamp_to_datetime(timestamp);
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000,
+                start,
+                start + 10 * MS_PER_DAY,
+            )
+            .unwrap();
 
-            assert_eq!(dt.year, 2024);
-            assert_eq!(dt.month, 10);
-            assert_eq!(dt.day, 21);
-            assert_eq!(dt.hour, 12);
-            assert_eq!(dt.minute, 0);
-            assert_eq!(dt.second, 0);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 4 * MS_PER_DAY,
+            );
+            contract.suspend_vesting(beneficiary).unwrap();
 
-            // Test formatting
-            let formatted = contract.format_datetime(dt);
-            let expected = b"2024-10-21 12:00:00";
-            assert_eq!(&formatted[..], expected);
+            // Well past the original end, the schedule is still where it was paused
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 20 * MS_PER_DAY,
+            );
+            assert_eq!(contract.vesting_progress_bps(beneficiary), Some(4_000));
+            assert_eq!(contract.is_fully_vested(beneficiary), Some(false));
         }
 
         #[ink::test]
-        fn test_leap_year() {
-            let contract = VestingScheduler::new();
+        fn test_propose_vesting_zero_start_means_now() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let admin: H160 = accounts.bob.into();
+            let beneficiary = H160::from([177u8; 20]);
+            let now = 1729512000000u64;
 
-            // Test leap year: Mar 1, 2024
-            let leap_day = 1709251200000u64; // 2024-03-01 00:00:00 UTC
-            let dt = contract.timestamp_to_datetime(leap_day);
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            contract.set_admin(admin, true).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(now);
 
-            assert_eq!(dt.year, 2024);
-            assert_eq!(dt.month, 3);
-            assert_eq!(dt.day, 1);
+            ink::env::test::set_caller(admin);
+            let id = contract
+                .propose_vesting(beneficiary, 1_000, 0, now + MS_PER_DAY)
+                .unwrap();
+            let (schedule, _, _) = contract.get_proposal(id).unwrap();
+            assert_eq!(schedule.start_time, now);
         }
 
         #[ink::test]
-        fn test_vesting_not_started() {
+        fn test_extend_vesting_respects_max_duration() {
             let accounts = ink::env::test::default_accounts();
-            let owner: H160 = accounts.alice.into(); // Convert AccountId to H160
-            let beneficiary: H160 = H160::from([2u8; 20]);
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([178u8; 20]);
+            let start = 1729512000000u64;
 
-            let current = 1729512000000u64;
-            let future_start = current + (10 * 24 * 60 * 60 * 1000); // 10 days from now
-            let future_end = future_start + (100 * 24 * 60 * 60 * 1000);
+            ink::env::test::set_caller(owner);
+            let mut contract = VestingScheduler::new();
+            contract.set_max_duration(100 * MS_PER_DAY).unwrap();
+            create_schedule(
+                &mut contract,
+                beneficiary,
+                1_000,
+                start,
+                start + 90 * MS_PER_DAY,
+            )
+            .unwrap();
 
-            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(current);
+            assert_eq!(
+                contract.extend_vesting(beneficiary, start + 101 * MS_PER_DAY),
+                Err(Error::DurationTooLong)
+            );
+            assert_eq!(
+                contract.extend_vesting(beneficiary, start + 100 * MS_PER_DAY),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn test_extend_vesting_rejects_revoked_and_tranche_schedules() {
+            let accounts = ink::env::test::default_accounts();
+            let owner: H160 = accounts.alice.into();
+            let revoked = H160::from([188u8; 20]);
+            let monthly = H160::from([189u8; 20]);
+            let stepped = H160::from([190u8; 20]);
+            let start = 1729512000000u64;
+            let end = start + 100 * MS_PER_DAY;
 
-            // Set caller to owner BEFORE creating contract
             ink::env::test::set_caller(owner);
             let mut contract = VestingScheduler::new();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start);
+            create_schedule(&mut contract, revoked, 1_000, start, end).unwrap();
+            contract
+                .create_monthly_vesting(monthly, 1_200, start, 12, 15)
+                .unwrap();
+            contract
+                .create_vesting_schedule_with_kind(
+                    stepped,
+                    1_000,
+                    start,
+                    end,
+                    VestingKind::Stepped { intervals: 4 },
+                )
+                .unwrap();
 
-            let result =
-                contract.create_vesting_schedule(beneficiary, 1_000_000, future_start, future_end);
-            assert!(
-                result.is_ok(),
-                "create_vesting_schedule failed: {:?}",
-                result
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 10 * MS_PER_DAY,
+            );
+            contract.revoke_vesting(revoked).unwrap();
+            assert_eq!(
+                contract.extend_vesting(revoked, end + MS_PER_DAY),
+                Err(Error::AlreadyRevoked)
             );
 
-            // Switch to beneficiary to claim
-            ink::env::test::set_caller(beneficiary);
-
-            // Try to claim before vesting starts
-            let result = contract.claim_vested();
-            assert_eq!(result, Err(Error::VestingNotStarted));
+            // Re-anchoring would move the unlock dates, so the schedules stay as they were
+            let before = contract.get_vesting_schedule(monthly).unwrap();
+            assert_eq!(
+                contract.extend_vesting(monthly, before.end_time + 30 * MS_PER_DAY),
+                Err(Error::UnsupportedKind)
+            );
+            assert_eq!(
+                contract.extend_vesting(stepped, end + MS_PER_DAY),
+                Err(Error::UnsupportedKind)
+            );
+            let after = contract.get_vesting_schedule(monthly).unwrap();
+            assert_eq!(after.start_time, before.start_time);
+            assert_eq!(after.end_time, before.end_time);
         }
 
         #[ink::test]
-        fn test_readable_schedule_view() {
+        fn test_top_up_tranche_schedule_only_before_start() {
             let accounts = ink::env::test::default_accounts();
-            let owner: H160 = accounts.alice.into(); // Convert AccountId to H160
-            let beneficiary: H160 = H160::from([3u8; 20]);
+            let owner: H160 = accounts.alice.into();
+            let beneficiary = H160::from([191u8; 20]);
+            let start = 1729512000000u64;
 
-            // Set caller to owner BEFORE creating contract
             ink::env::test::set_caller(owner);
             let mut contract = VestingScheduler::new();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(start - MS_PER_DAY);
+            contract
+                .create_monthly_vesting(beneficiary, 1_200, start, 12, 15)
+                .unwrap();
 
-            let start = 1729512000000u64; // 2024-10-21 12:00:00
-            let end = 1737374400000u64; // 2025-01-20 12:00:00
+            // Before the start nothing is re-anchored, so the months stay in place
+            assert_eq!(contract.top_up_vesting(beneficiary, 1_200), Ok(()));
+            let schedule = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(schedule.total_amount, 2_400);
+            assert_eq!(schedule.start_time, start);
 
-            let result = contract.create_vesting_schedule(beneficiary, 1_000_000, start, end);
-            assert!(
-                result.is_ok(),
-                "create_vesting_schedule failed: {:?}",
-                result
+            // Once running, a top-up would move the unlock dates
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                start + 40 * MS_PER_DAY,
             );
-
-            let result = contract.get_vesting_schedule_readable(beneficiary);
-            assert!(result.is_some());
-
-            let (schedule, start_readable, end_readable) = result.unwrap();
-            assert_eq!(schedule.total_amount, 1_000_000);
-            assert_eq!(&start_readable[..], b"2024-10-21 12:00:00");
-            assert_eq!(&end_readable[..], b"2025-01-20 12:00:00");
+            assert_eq!(
+                contract.top_up_vesting(beneficiary, 1_200),
+                Err(Error::UnsupportedKind)
+            );
+            let unchanged = contract.get_vesting_schedule(beneficiary).unwrap();
+            assert_eq!(unchanged.total_amount, 2_400);
+            assert_eq!(unchanged.start_time, start);
         }
     }
 }